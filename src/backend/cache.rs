@@ -0,0 +1,202 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::backend::privileged::Privileged;
+
+/// Önbellekteki tek bir .pisi dosyası
+#[derive(Debug, Clone)]
+pub struct CachedPackage {
+    pub path: PathBuf,
+    pub name: String,
+    pub version: String,
+    pub release: u32,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Önbellek kullanım raporu, paket adına göre gruplanmış
+#[derive(Debug, Clone, Default)]
+pub struct CacheReport {
+    /// Her paketin sürümleri, en yeniden en eskiye
+    pub packages: BTreeMap<String, Vec<CachedPackage>>,
+    pub total_size: u64,
+}
+
+impl CacheReport {
+    pub fn file_count(&self) -> usize {
+        self.packages.values().map(Vec::len).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CleanupPolicy {
+    /// Her paketin en yeni N sürümünü tut
+    KeepLastVersions(usize),
+    /// Artık kurulu olmayan paketlerin dosyalarını sil
+    RemoveNotInstalled,
+    /// Toplam boyut sınırın altına inene kadar en eskilerden sil
+    TrimToSize(u64),
+    /// Her şeyi sil
+    ClearAll,
+}
+
+pub struct CacheManager;
+
+impl CacheManager {
+    pub fn scan(directory: &Path) -> Result<CacheReport> {
+        let entries = std::fs::read_dir(directory)
+            .map_err(|e| anyhow::anyhow!("Failed to read cache directory {}: {}", directory.display(), e))?;
+
+        let mut report = CacheReport::default();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "pisi") {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+
+            let file_stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let (name, version, release) = Self::parse_file_name(&file_stem);
+            report.total_size += metadata.len();
+            report.packages.entry(name.clone()).or_default().push(CachedPackage {
+                path,
+                name,
+                version,
+                release,
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+
+        for versions in report.packages.values_mut() {
+            versions.sort_by(|a, b| b.release.cmp(&a.release).then(b.modified.cmp(&a.modified)));
+        }
+
+        Ok(report)
+    }
+
+    /// "firefox-115.0-12-p2-x86_64" -> ("firefox", "115.0", 12)
+    ///
+    /// Eski paketlerde dağıtım/mimari eki olmayabilir ("name-version-release-build").
+    fn parse_file_name(file_stem: &str) -> (String, String, u32) {
+        let parts: Vec<&str> = file_stem.split('-').collect();
+
+        // Sürüm rakamla başlayan ve hemen ardından sayısal release gelen ilk alandır
+        for version_index in 1..parts.len().saturating_sub(1) {
+            let version = parts[version_index];
+            if !version.starts_with(|c: char| c.is_ascii_digit()) {
+                continue;
+            }
+            if let Ok(release) = parts[version_index + 1].parse::<u32>() {
+                let name = parts[..version_index].join("-");
+                return (name, version.to_string(), release);
+            }
+        }
+
+        (file_stem.to_string(), String::new(), 0)
+    }
+
+    /// Politikaya göre silinecek dosyaların listesi (önizleme)
+    pub fn plan(report: &CacheReport, policy: &CleanupPolicy, installed: &HashSet<String>) -> Vec<CachedPackage> {
+        match policy {
+            CleanupPolicy::KeepLastVersions(keep) => report
+                .packages
+                .values()
+                .flat_map(|versions| versions.iter().skip(*keep).cloned())
+                .collect(),
+            CleanupPolicy::RemoveNotInstalled => report
+                .packages
+                .iter()
+                .filter(|(name, _)| !installed.contains(*name))
+                .flat_map(|(_, versions)| versions.iter().cloned())
+                .collect(),
+            CleanupPolicy::TrimToSize(limit) => {
+                // Önce eski sürümler, sonra en eski tarihliler gitsin
+                let mut candidates: Vec<(bool, &CachedPackage)> = report
+                    .packages
+                    .values()
+                    .flat_map(|versions| {
+                        versions.iter().enumerate().map(|(index, package)| (index == 0, package))
+                    })
+                    .collect();
+                candidates.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.modified.cmp(&b.1.modified)));
+
+                let mut remaining = report.total_size;
+                candidates
+                    .into_iter()
+                    .take_while(|(_, package)| {
+                        let over = remaining > *limit;
+                        if over {
+                            remaining = remaining.saturating_sub(package.size);
+                        }
+                        over
+                    })
+                    .map(|(_, package)| package.clone())
+                    .collect()
+            }
+            CleanupPolicy::ClearAll => report.packages.values().flatten().cloned().collect(),
+        }
+    }
+
+    /// Dosyaları sil, yetki yetmezse pkexec ile tekrar dene
+    pub fn delete(files: &[CachedPackage]) -> Result<u64> {
+        let mut freed = 0;
+        let mut needs_root = Vec::new();
+
+        for file in files {
+            match std::fs::remove_file(&file.path) {
+                Ok(()) => freed += file.size,
+                Err(e) if e.kind() == ErrorKind::PermissionDenied => needs_root.push(file),
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(anyhow::anyhow!("Failed to delete {}: {}", file.path.display(), e)),
+            }
+        }
+
+        if !needs_root.is_empty() {
+            let output = Privileged::command("rm")
+            .arg("-f")
+            .args(needs_root.iter().map(|file| &file.path))
+            .output()?;
+
+            if !output.status.success() {
+                let error = String::from_utf8_lossy(&output.stderr);
+                return Err(anyhow::anyhow!("Failed to clean package cache: {}", error));
+            }
+            freed += needs_root.iter().map(|file| file.size).sum::<u64>();
+        }
+
+        println!("Package cache cleanup freed {} bytes", freed);
+        Ok(freed)
+    }
+
+    /// Önbellek boyut sınırını aşıyorsa silinecek en eski dosyalar
+    pub fn over_limit(directory: &Path, limit_mb: u32) -> Result<Vec<CachedPackage>> {
+        let report = Self::scan(directory)?;
+        let limit = limit_mb as u64 * 1024 * 1024;
+        if limit == 0 || report.total_size <= limit {
+            return Ok(Vec::new());
+        }
+
+        Ok(Self::plan(&report, &CleanupPolicy::TrimToSize(limit), &HashSet::new()))
+    }
+}
+
+/// Bayt değerini okunabilir hale getir
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
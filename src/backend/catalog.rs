@@ -0,0 +1,198 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use crate::backend::xml_parser::{Component, PackageInfo, XmlParser};
+
+/// Katalogdaki bir paketin tanıtıcısı; katalog yeniden kurulana kadar geçerlidir
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PackageId(u32);
+
+impl PackageId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Tekrarlanan metinlerin (component, depo, lisans, mimari) tek kopyası
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    strings: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    pub fn intern(&mut self, text: &str) -> Arc<str> {
+        if let Some(existing) = self.strings.get(text) {
+            return existing.clone();
+        }
+        let interned: Arc<str> = Arc::from(text);
+        self.strings.insert(interned.clone());
+        interned
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+/// Tüm depolardan yüklenen paketler ve kurulum durumları
+///
+/// Grid, detay paneli, kenar çubuğu ve arama aynı katalogdan okur; paketlere
+/// `PackageId` ile başvurulur, kopyalanmaz.
+#[derive(Debug, Clone, Default)]
+pub struct PackageCatalog {
+    packages: Vec<PackageInfo>,
+    interner: Interner,
+    by_name: HashMap<String, PackageId>,
+    /// Ham PartOf değerine göre ("desktop.kde")
+    by_component: BTreeMap<Arc<str>, Vec<PackageId>>,
+    by_repository: BTreeMap<Arc<str>, Vec<PackageId>>,
+    components: Vec<Component>,
+    /// Katalogda olmayanlar da dahil kurulu paket adları
+    installed_names: HashSet<String>,
+    installed: Vec<PackageId>,
+    /// Kurulu paketlerin "sürüm-release" bilgisi
+    installed_versions: HashMap<String, String>,
+    upgradable_names: Vec<String>,
+    upgradable: HashSet<String>,
+}
+
+impl PackageCatalog {
+    /// Paketler depo önceliği sırasıyla gelir; aynı adın ilki kalır
+    pub fn new(packages: Vec<PackageInfo>) -> Self {
+        let mut catalog = Self::default();
+
+        for mut package in packages {
+            if catalog.by_name.contains_key(&package.name) {
+                continue;
+            }
+            package.part_of = catalog.interner.intern(&package.part_of);
+            package.repository = catalog.interner.intern(&package.repository);
+            package.license = catalog.interner.intern(&package.license);
+            package.architecture = catalog.interner.intern(&package.architecture);
+
+            let id = PackageId(catalog.packages.len() as u32);
+            catalog.by_name.insert(package.name.clone(), id);
+            catalog.by_component.entry(package.part_of.clone()).or_default().push(id);
+            catalog.by_repository.entry(package.repository.clone()).or_default().push(id);
+            catalog.packages.push(package);
+        }
+
+        catalog.components = XmlParser::parse_components(&catalog.packages);
+        println!(
+            "Catalog: {} packages, {} components, {} interned strings",
+            catalog.packages.len(),
+            catalog.by_component.len(),
+            catalog.interner.len()
+        );
+        catalog
+    }
+
+    /// Paketler değişirken kurulum durumlarını koru
+    pub fn replace_packages(&mut self, packages: Vec<PackageInfo>) {
+        let installed = std::mem::take(&mut self.installed_names);
+        let upgradable = std::mem::take(&mut self.upgradable_names);
+        let versions = std::mem::take(&mut self.installed_versions);
+        *self = Self::new(packages);
+        self.set_installed(installed);
+        self.set_installed_versions(versions);
+        self.set_upgradable(upgradable);
+    }
+
+    pub fn len(&self) -> usize {
+        self.packages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    pub fn packages(&self) -> &[PackageInfo] {
+        &self.packages
+    }
+
+    pub fn get(&self, id: PackageId) -> Option<&PackageInfo> {
+        self.packages.get(id.index())
+    }
+
+    pub fn id(&self, name: &str) -> Option<PackageId> {
+        self.by_name.get(name).copied()
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&PackageInfo> {
+        self.id(name).and_then(|id| self.get(id))
+    }
+
+    /// Katalog sırasıyla tüm tanıtıcılar
+    pub fn ids(&self) -> impl Iterator<Item = PackageId> {
+        (0..self.packages.len() as u32).map(PackageId)
+    }
+
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+
+    /// Ham PartOf değerindeki paketler
+    pub fn in_component(&self, part_of: &str) -> &[PackageId] {
+        self.by_component.get(part_of).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn in_repository(&self, repository: &str) -> &[PackageId] {
+        self.by_repository.get(repository).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn repositories(&self) -> impl Iterator<Item = &str> {
+        self.by_repository.keys().map(|repo| &**repo)
+    }
+
+    /// `pisi list-installed` çıktısıyla kurulu indeksini yenile
+    pub fn set_installed(&mut self, names: impl IntoIterator<Item = String>) {
+        self.installed_names = names.into_iter().filter(|name| !name.is_empty()).collect();
+        self.rebuild_installed();
+    }
+
+    pub fn set_installed_versions(&mut self, versions: HashMap<String, String>) {
+        self.installed_versions = versions;
+    }
+
+    pub fn set_upgradable(&mut self, names: Vec<String>) {
+        self.upgradable = names.iter().cloned().collect();
+        self.upgradable_names = names;
+    }
+
+    pub fn installed_names(&self) -> &HashSet<String> {
+        &self.installed_names
+    }
+
+    /// Katalogdaki kurulu paketler, katalog sırasıyla
+    pub fn installed(&self) -> &[PackageId] {
+        &self.installed
+    }
+
+    pub fn is_installed(&self, name: &str) -> bool {
+        self.installed_names.contains(name)
+    }
+
+    /// Kurulu sürüm ("115.0-12"); veritabanı okunamadıysa None
+    pub fn installed_version(&self, name: &str) -> Option<&str> {
+        self.installed_versions.get(name).map(String::as_str)
+    }
+
+    /// Güncellemesi olan paketler (hold listesi uygulanmamış)
+    pub fn upgradable_names(&self) -> &[String] {
+        &self.upgradable_names
+    }
+
+    pub fn is_upgradable(&self, name: &str) -> bool {
+        self.upgradable.contains(name)
+    }
+
+    fn rebuild_installed(&mut self) {
+        self.installed = self
+            .ids()
+            .filter(|&id| self.installed_names.contains(&self.packages[id.index()].name))
+            .collect();
+    }
+}
@@ -0,0 +1,65 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::backend::filter::PackageFilter;
+use crate::backend::search::SearchMode;
+
+/// Dışa aktarılan dosyanın biçim sürümü
+const COLLECTIONS_FILE_VERSION: u32 = 1;
+
+/// Adı verilmiş arama + filtre birleşimi; kenar çubuğunda canlı sayısıyla görünür
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartCollection {
+    pub name: String,
+    /// Boşsa tüm paketlere yalnızca filtre uygulanır
+    #[serde(default)]
+    pub query: String,
+    #[serde(default)]
+    pub search_mode: SearchMode,
+    #[serde(default)]
+    pub filter: PackageFilter,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CollectionsFile {
+    version: u32,
+    collections: Vec<SmartCollection>,
+}
+
+impl SmartCollection {
+    /// Koleksiyonları başka bir makinede içe aktarılabilecek JSON dosyasına yaz
+    pub fn export(collections: &[SmartCollection], path: &Path) -> Result<()> {
+        let file = CollectionsFile {
+            version: COLLECTIONS_FILE_VERSION,
+            collections: collections.to_vec(),
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&file)?)?;
+        println!("Exported {} smart collections to {}", collections.len(), path.display());
+        Ok(())
+    }
+
+    /// Dışa aktarılmış dosyayı oku; elle yazılmış düz bir liste de kabul edilir
+    pub fn import(path: &Path) -> Result<Vec<SmartCollection>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Could not read {}: {}", path.display(), e))?;
+
+        let collections = match serde_json::from_str::<CollectionsFile>(&content) {
+            Ok(file) => file.collections,
+            Err(_) => serde_json::from_str::<Vec<SmartCollection>>(&content)
+                .map_err(|e| anyhow::anyhow!("{} is not a smart collection file: {}", path.display(), e))?,
+        };
+
+        Ok(collections
+            .into_iter()
+            .filter(|collection| !collection.name.trim().is_empty())
+            .collect())
+    }
+
+    /// Aynı adlı koleksiyonu değiştir, yoksa sona ekle
+    pub fn upsert(collections: &mut Vec<SmartCollection>, collection: SmartCollection) {
+        match collections.iter_mut().find(|existing| existing.name == collection.name) {
+            Some(existing) => *existing = collection,
+            None => collections.push(collection),
+        }
+    }
+}
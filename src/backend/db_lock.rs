@@ -0,0 +1,133 @@
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+
+/// Pisi'nin veritabanı işlemleri sırasında flock ile kilitlediği dosya
+pub const PISI_LOCK_FILE: &str = "/var/lock/subsys/pisi";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub command: String,
+}
+
+pub struct DbLock;
+
+/// Kilitli dosyanın kimliği; inode numarası tek başına cihazlar arasında tekrar edebilir
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileId {
+    major: u32,
+    minor: u32,
+    inode: u64,
+}
+
+impl FileId {
+    /// glibc `major()`/`minor()` ile aynı st_dev ayrıştırması
+    fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        let dev = metadata.dev();
+        Self {
+            major: (((dev >> 8) & 0xfff) | ((dev >> 32) & !0xfff)) as u32,
+            minor: ((dev & 0xff) | ((dev >> 12) & !0xff)) as u32,
+            inode: metadata.ino(),
+        }
+    }
+}
+
+impl DbLock {
+    /// Kilidi tutan süreci döndür, kilit boştaysa None
+    pub fn holder() -> Option<LockHolder> {
+        Self::holder_of(Path::new(PISI_LOCK_FILE))
+    }
+
+    pub fn holder_of(lock_file: &Path) -> Option<LockHolder> {
+        let file = FileId::from_metadata(&std::fs::metadata(lock_file).ok()?);
+        let locks = std::fs::read_to_string("/proc/locks").ok()?;
+
+        locks
+        .lines()
+        .filter_map(Self::parse_lock_line)
+        .find(|(_, lock_file)| *lock_file == file)
+        .map(|(pid, _)| LockHolder {
+            pid,
+            command: Self::process_command(pid),
+        })
+    }
+
+    /// `/proc/locks` satırı: "1: FLOCK  ADVISORY  WRITE 1234 08:01:131 0 EOF";
+    /// major ve minor onaltılık, inode onluk yazılır
+    fn parse_lock_line(line: &str) -> Option<(u32, FileId)> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        // Bekleyen kilitler "1: -> FLOCK ..." şeklinde gelir, onları atla
+        if parts.len() < 6 || parts[1] == "->" {
+            return None;
+        }
+
+        let pid = parts[4].parse().ok()?;
+        let mut device = parts[5].split(':');
+        let file = FileId {
+            major: u32::from_str_radix(device.next()?, 16).ok()?,
+            minor: u32::from_str_radix(device.next()?, 16).ok()?,
+            inode: device.next()?.parse().ok()?,
+        };
+        Some((pid, file))
+    }
+
+    fn process_command(pid: u32) -> String {
+        std::fs::read(format!("/proc/{}/cmdline", pid))
+        .map(|raw| {
+            raw.split(|&b| b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect::<Vec<String>>()
+            .join(" ")
+        })
+        .ok()
+        .filter(|cmd| !cmd.is_empty())
+        .unwrap_or_else(|| "unknown process".to_string())
+    }
+}
+
+impl std::fmt::Display for LockHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (PID {})", self.command, self.pid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(major: u32, minor: u32, inode: u64) -> FileId {
+        FileId { major, minor, inode }
+    }
+
+    #[test]
+    fn parses_proc_locks_lines() {
+        assert_eq!(
+            DbLock::parse_lock_line("1: FLOCK  ADVISORY  WRITE 2413 08:01:1837295 0 EOF"),
+            Some((2413, file(8, 1, 1837295)))
+        );
+        assert_eq!(
+            DbLock::parse_lock_line("12: POSIX  ADVISORY  READ 918 103:02:6816138 128 128"),
+            Some((918, file(0x103, 2, 6816138)))
+        );
+        assert_eq!(
+            DbLock::parse_lock_line("3: FLOCK  ADVISORY  WRITE 771 00:1a:42 0 EOF"),
+            Some((771, file(0, 0x1a, 42)))
+        );
+    }
+
+    #[test]
+    fn skips_waiting_and_malformed_lines() {
+        assert_eq!(DbLock::parse_lock_line("1: -> FLOCK  ADVISORY  WRITE 2500 08:01:1837295 0 EOF"), None);
+        assert_eq!(DbLock::parse_lock_line("4: OFDLCK ADVISORY  READ  -1 00:06:9 0 EOF"), None);
+        assert_eq!(DbLock::parse_lock_line("5: FLOCK  ADVISORY  WRITE 1234 1837295 0 EOF"), None);
+        assert_eq!(DbLock::parse_lock_line(""), None);
+    }
+
+    #[test]
+    fn same_inode_on_another_device_does_not_match() {
+        let (_, locked) = DbLock::parse_lock_line("1: FLOCK  ADVISORY  WRITE 2413 08:01:1837295 0 EOF").unwrap();
+        assert_ne!(locked, file(8, 2, 1837295));
+        assert_ne!(locked, file(0xfd, 1, 1837295));
+    }
+}
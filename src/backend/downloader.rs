@@ -0,0 +1,370 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use base64::prelude::{Engine, BASE64_STANDARD};
+use crate::config::{CacheSettings, ProxySettings};
+
+/// İndirme ayarları, `ProxySettings` ve `CacheSettings`'ten türetilir
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    /// Kimlik bilgisi içermeyen proxy adresleri: "http://host:port"
+    pub http_proxy: Option<String>,
+    pub https_proxy: Option<String>,
+    pub ftp_proxy: Option<String>,
+    /// Proxy kullanıcısı ("DOMAIN\kullanıcı") ve parolası, kodlanmadan
+    pub proxy_credentials: Option<(String, String)>,
+    pub connect_timeout: Duration,
+    pub read_timeout: Duration,
+    /// KB/s cinsinden indirme hızı sınırı
+    pub bandwidth_limit: Option<u32>,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            http_proxy: None,
+            https_proxy: None,
+            ftp_proxy: None,
+            proxy_credentials: None,
+            connect_timeout: Duration::from_secs(15),
+            read_timeout: Duration::from_secs(30),
+            bandwidth_limit: None,
+        }
+    }
+}
+
+impl DownloadOptions {
+    pub fn from_settings(proxy: &ProxySettings, cache: &CacheSettings) -> Self {
+        let mut options = Self {
+            bandwidth_limit: cache.bandwidth_limit(),
+            ..Self::default()
+        };
+        if !proxy.use_proxy {
+            return options;
+        }
+
+        let http = Self::proxy_address(&proxy.http_proxy);
+        if proxy.use_http_proxy_for_all {
+            options.https_proxy = http.clone();
+            options.ftp_proxy = http.clone();
+        } else {
+            options.https_proxy = Self::proxy_address(&proxy.https_proxy);
+            options.ftp_proxy = Self::proxy_address(&proxy.ftp_proxy);
+        }
+        options.http_proxy = http;
+
+        // pisi.conf'a yazılanla aynı kural: kimlik bilgileri olduğu gibi kullanılır
+        if !proxy.username.is_empty() {
+            let user = if proxy.domain.is_empty() {
+                proxy.username.clone()
+            } else {
+                format!("{}\\{}", proxy.domain, proxy.username)
+            };
+            options.proxy_credentials = Some((user, proxy.password.clone()));
+        }
+        options
+    }
+
+    /// "host:port" ya da "http://host:port" -> "http://host:port"
+    ///
+    /// Proxy'ye TLS ile bağlanılamaz; "https://" yazılmışsa düz HTTP proxy
+    /// olarak kullanılır (https adresleri yine CONNECT ile tünellenir).
+    fn proxy_address(address: &str) -> Option<String> {
+        let address = address.trim().trim_end_matches('/');
+        if address.is_empty() {
+            return None;
+        }
+
+        let (scheme, host) = match address.split_once("://") {
+            Some(("https", host)) => ("http", host),
+            Some((scheme, host)) => (scheme, host),
+            None => ("http", address),
+        };
+        Some(format!("{}://{}", scheme, host))
+    }
+}
+
+/// Koşullu istek için saklanan sunucu bilgileri
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct CacheValidators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// If-Range değeri; zayıf ETag kullanılamaz, yoksa Last-Modified denenir
+    fn if_range(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DownloadOutcome {
+    /// Dosya indirildi (toplam bayt)
+    Downloaded(u64),
+    /// Sunucu 304 döndü, yerel kopya güncel
+    NotModified,
+}
+
+pub struct Downloader {
+    options: DownloadOptions,
+}
+
+impl Downloader {
+    pub fn new(options: DownloadOptions) -> Self {
+        Self { options }
+    }
+
+    fn agent_for(&self, url: &str) -> Result<ureq::Agent> {
+        let mut builder = ureq::AgentBuilder::new()
+        .timeout_connect(self.options.connect_timeout)
+        .timeout_read(self.options.read_timeout);
+
+        if let Some(proxy) = self.proxy_for(url) {
+            // ureq kimlik bilgisini adresin içinden, çözmeden okur: son '@'
+            // öncesi ve ilk ':' sonrası parola olduğu için özel karakterler sorun olmaz
+            let proxy = match &self.options.proxy_credentials {
+                Some((user, password)) => {
+                    let (scheme, host) = proxy.split_once("://").unwrap_or(("http", proxy));
+                    format!("{}://{}:{}@{}", scheme, user, password, host)
+                }
+                None => proxy.clone(),
+            };
+            builder = builder.proxy(ureq::Proxy::new(proxy)?);
+        }
+
+        Ok(builder.build())
+    }
+
+    fn proxy_for(&self, url: &str) -> Option<&String> {
+        if url.starts_with("https://") {
+            self.options.https_proxy.as_ref()
+        } else {
+            self.options.http_proxy.as_ref()
+        }
+    }
+
+    /// GET isteği; http adresleri proxy üzerinden giderken kimlik bilgisi
+    /// başlıkta gönderilir (ureq bunu yalnızca https için CONNECT'te yapar)
+    fn get(&self, url: &str) -> Result<ureq::Request> {
+        let mut request = self.agent_for(url)?.get(url);
+        if url.starts_with("http://") && self.proxy_for(url).is_some() {
+            if let Some((user, password)) = &self.options.proxy_credentials {
+                let credentials = BASE64_STANDARD.encode(format!("{}:{}", user, password));
+                request = request.set("Proxy-Authorization", &format!("Basic {}", credentials));
+            }
+        }
+        Ok(request)
+    }
+
+    /// http(s)://, ftp://, file:// ya da yerel dosya yolundan içeriği oku
+    pub fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+        if url.starts_with("ftp://") {
+            return self.fetch_ftp(url);
+        }
+        if !Self::is_remote(url) {
+            let path = url.strip_prefix("file://").unwrap_or(url);
+            return fs::read(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e));
+        }
+
+        let response = self.get(url)?
+        .call()
+        .map_err(|e| anyhow::anyhow!("Failed to download {}: {}", url, e))?;
+
+        let mut bytes = Vec::new();
+        self.copy_throttled(&mut response.into_reader(), &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// URL'yi `dest` dosyasına indir
+    ///
+    /// ETag/Last-Modified saklanır ve sonraki indirmede koşullu istek yapılır.
+    /// Yarım kalan indirme `dest.part` dosyasından Range ile devam ettirilir;
+    /// yarım dosyanın sürümü `dest.part.meta`'daki ETag ya da Last-Modified ile
+    /// If-Range'de doğrulanır, ikisi de yoksa indirme baştan yapılır.
+    pub fn download_to(&self, url: &str, dest: &Path) -> Result<DownloadOutcome> {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Koşullu istek ve devam ettirme yalnızca HTTP'de var
+        if !Self::is_remote(url) {
+            let bytes = self.fetch(url)?;
+            fs::write(dest, &bytes)?;
+            return Ok(DownloadOutcome::Downloaded(bytes.len() as u64));
+        }
+
+        let part_path = Self::sibling(dest, "part");
+        let part_meta_path = Self::sibling(dest, "part.meta");
+        let meta_path = Self::sibling(dest, "meta");
+        let validators = CacheValidators::load(&meta_path);
+        let part_validators = CacheValidators::load(&part_meta_path);
+        // Doğrulanamayan yarım dosyaya eklemek farklı sürümleri karıştırabilir
+        let if_range = part_validators.if_range();
+        let resume_from = match if_range {
+            Some(_) => fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0),
+            None => 0,
+        };
+
+        let mut request = self.get(url)?;
+        if let Some(if_range) = if_range.filter(|_| resume_from > 0) {
+            request = request
+            .set("Range", &format!("bytes={}-", resume_from))
+            // Sunucudaki dosya değiştiyse baştan gönderilsin
+            .set("If-Range", if_range);
+        } else if dest.exists() {
+            if let Some(etag) = &validators.etag {
+                request = request.set("If-None-Match", etag);
+            }
+            if let Some(last_modified) = &validators.last_modified {
+                request = request.set("If-Modified-Since", last_modified);
+            }
+        }
+
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::Status(304, _)) => return Ok(DownloadOutcome::NotModified),
+            Err(ureq::Error::Status(416, _)) => {
+                // .part dosyası zaten tam ya da bozuk, baştan başla
+                fs::remove_file(&part_path)?;
+                let _ = fs::remove_file(&part_meta_path);
+                return self.download_to(url, dest);
+            }
+            Err(e) => return Err(anyhow::anyhow!("Failed to download {}: {}", url, e)),
+        };
+
+        if response.status() == 304 {
+            return Ok(DownloadOutcome::NotModified);
+        }
+
+        let resumed = response.status() == 206;
+        // 206 yanıtı doğrulayıcıları tekrarlamayabilir; yarım dosyanınkiler geçerlidir
+        let new_validators = CacheValidators {
+            etag: response.header("ETag").map(|s| s.to_string())
+                .or_else(|| part_validators.etag.clone().filter(|_| resumed)),
+            last_modified: response.header("Last-Modified").map(|s| s.to_string())
+                .or_else(|| part_validators.last_modified.clone().filter(|_| resumed)),
+        };
+
+        let mut file = if resumed {
+            OpenOptions::new().append(true).open(&part_path)?
+        } else {
+            // Yarıda kalırsa devam ederken bu sürüm istenir
+            fs::write(&part_meta_path, serde_json::to_string(&new_validators)?)?;
+            File::create(&part_path)?
+        };
+
+        let written = self.copy_throttled(&mut response.into_reader(), &mut file)?;
+        let total = if resumed { resume_from + written } else { written };
+        file.sync_all()?;
+
+        fs::rename(&part_path, dest)?;
+        fs::write(&meta_path, serde_json::to_string(&new_validators)?)?;
+        let _ = fs::remove_file(&part_meta_path);
+
+        Ok(DownloadOutcome::Downloaded(total))
+    }
+
+    /// ureq FTP bilmez; ftp:// depoları curl ile, varsa ftp proxy'si üzerinden indir
+    fn fetch_ftp(&self, url: &str) -> Result<Vec<u8>> {
+        let mut command = Command::new("curl");
+        command
+        .args(["--fail", "--silent", "--show-error", "--config", "-"])
+        .arg("--connect-timeout")
+        .arg(self.options.connect_timeout.as_secs().to_string());
+        if let Some(limit) = self.options.bandwidth_limit {
+            command.arg("--limit-rate").arg(format!("{}k", limit));
+        }
+
+        // Parola komut satırında görünmesin diye curl'e stdin'den verilir
+        let mut config = String::new();
+        if let Some(proxy) = &self.options.ftp_proxy {
+            config.push_str(&format!("proxy = \"{}\"\n", curl_quote(proxy)));
+            if let Some((user, password)) = &self.options.proxy_credentials {
+                config.push_str(&format!("proxy-user = \"{}:{}\"\n", curl_quote(user), curl_quote(password)));
+            }
+        }
+
+        let mut child = command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to download {}: curl is required for ftp:// ({})", url, e))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(config.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+
+        if output.status.success() {
+            Ok(output.stdout)
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow::anyhow!("Failed to download {}: {}", url, error.trim()))
+        }
+    }
+
+    /// Okunanı yaz; bant genişliği sınırı varsa ortalama hızı sınırın altında tut
+    fn copy_throttled(&self, reader: &mut impl Read, writer: &mut impl Write) -> Result<u64> {
+        let mut buffer = [0u8; 16 * 1024];
+        let mut total = 0u64;
+        let started = Instant::now();
+
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            writer.write_all(&buffer[..read])?;
+            total += read as u64;
+
+            if let Some(limit) = self.options.bandwidth_limit {
+                let expected = Duration::from_secs_f64(total as f64 / (limit as f64 * 1024.0));
+                let elapsed = started.elapsed();
+                if expected > elapsed {
+                    std::thread::sleep(expected - elapsed);
+                }
+            }
+        }
+
+        Ok(total)
+    }
+
+    fn is_remote(url: &str) -> bool {
+        url.starts_with("http://") || url.starts_with("https://")
+    }
+
+    /// "pisi-index.xml.xz" -> "pisi-index.xml.xz.part"
+    fn sibling(path: &Path, extension: &str) -> PathBuf {
+        let mut name = path.file_name().unwrap_or_default().to_os_string();
+        name.push(".");
+        name.push(extension);
+        path.with_file_name(name)
+    }
+}
+
+impl Default for Downloader {
+    fn default() -> Self {
+        Self::new(DownloadOptions::default())
+    }
+}
+
+/// curl yapılandırma dosyasındaki çift tırnaklı değer için
+fn curl_quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,261 @@
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::backend::xml_parser::PackageInfo;
+
+/// Kartlarda gösterilen boyutlarla (`format_size`) aynı, 1024 tabanlı
+const MB: u64 = 1024 * 1024;
+
+/// Kurulum durumuna göre filtre
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum InstallState {
+    #[default]
+    Any,
+    Installed,
+    NotInstalled,
+    Upgradable,
+}
+
+impl InstallState {
+    pub const ALL: [InstallState; 4] = [Self::Any, Self::Installed, Self::NotInstalled, Self::Upgradable];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Any => "Any state",
+            Self::Installed => "Installed",
+            Self::NotInstalled => "Not installed",
+            Self::Upgradable => "Upgradable",
+        }
+    }
+}
+
+/// Boyut aralığı (MB); sınırı olmayan taraf None
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SizeRange {
+    pub min_mb: Option<u64>,
+    pub max_mb: Option<u64>,
+}
+
+impl SizeRange {
+    pub const ANY: SizeRange = SizeRange { min_mb: None, max_mb: None };
+
+    /// Filtre çubuğunda sunulan hazır aralıklar
+    pub const PRESETS: [SizeRange; 5] = [
+        Self::ANY,
+        SizeRange { min_mb: None, max_mb: Some(1) },
+        SizeRange { min_mb: Some(1), max_mb: Some(10) },
+        SizeRange { min_mb: Some(10), max_mb: Some(100) },
+        SizeRange { min_mb: Some(100), max_mb: None },
+    ];
+
+    pub fn is_any(&self) -> bool {
+        *self == Self::ANY
+    }
+
+    /// [alt, üst) aralığında mı
+    pub fn contains(&self, size: u64) -> bool {
+        self.min_mb.is_none_or(|min| size >= min * MB) && self.max_mb.is_none_or(|max| size < max * MB)
+    }
+}
+
+/// Son güncelleme tarihine göre filtre
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum UpdatedWithin {
+    #[default]
+    Any,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl UpdatedWithin {
+    pub const ALL: [UpdatedWithin; 5] = [Self::Any, Self::Week, Self::Month, Self::Quarter, Self::Year];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Any => "Any time",
+            Self::Week => "Last 7 days",
+            Self::Month => "Last 30 days",
+            Self::Quarter => "Last 90 days",
+            Self::Year => "Last year",
+        }
+    }
+
+    fn days(&self) -> Option<u64> {
+        match self {
+            Self::Any => None,
+            Self::Week => Some(7),
+            Self::Month => Some(30),
+            Self::Quarter => Some(90),
+            Self::Year => Some(365),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SortOrder {
+    /// Arama sırası ya da index sırası
+    #[default]
+    Default,
+    Name,
+    DownloadSize,
+    InstalledSize,
+    LastUpdated,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 5] = [
+        Self::Default,
+        Self::Name,
+        Self::DownloadSize,
+        Self::InstalledSize,
+        Self::LastUpdated,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Default => "Relevance",
+            Self::Name => "Name",
+            Self::DownloadSize => "Download size",
+            Self::InstalledSize => "Installed size",
+            Self::LastUpdated => "Last updated",
+        }
+    }
+}
+
+/// Etkin filtrelerden biri; filtre çubuğunda kaldırılabilir etiket olarak gösterilir
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterChip {
+    State(InstallState),
+    License(String),
+    Architecture(String),
+    Repository(String),
+    DownloadSize(SizeRange),
+    InstalledSize(SizeRange),
+    Updated(UpdatedWithin),
+}
+
+/// Paket listesine uygulanan filtreler ve sıralama
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PackageFilter {
+    pub state: InstallState,
+    pub license: Option<String>,
+    pub architecture: Option<String>,
+    pub repository: Option<String>,
+    pub download_size: SizeRange,
+    pub installed_size: SizeRange,
+    pub updated_within: UpdatedWithin,
+    pub sort: SortOrder,
+    pub descending: bool,
+}
+
+impl PackageFilter {
+    /// Filtrele ve sırala; `Default` sıralamada gelen sıra korunur
+    pub fn apply<'a>(
+        &self,
+        packages: Vec<&'a PackageInfo>,
+        is_installed: impl Fn(&str) -> bool,
+        has_update: impl Fn(&str) -> bool,
+    ) -> Vec<&'a PackageInfo> {
+        let since = self.updated_within.days().map(date_days_ago);
+
+        let mut packages: Vec<&PackageInfo> = packages
+            .into_iter()
+            .filter(|package| match self.state {
+                InstallState::Any => true,
+                InstallState::Installed => is_installed(&package.name),
+                InstallState::NotInstalled => !is_installed(&package.name),
+                InstallState::Upgradable => has_update(&package.name),
+            })
+            .filter(|package| self.license.as_ref().is_none_or(|license| *package.license == **license))
+            .filter(|package| self.architecture.as_ref().is_none_or(|arch| *package.architecture == **arch))
+            .filter(|package| self.repository.as_ref().is_none_or(|repo| *package.repository == **repo))
+            .filter(|package| self.download_size.contains(package.package_size))
+            .filter(|package| self.installed_size.contains(package.installed_size))
+            .filter(|package| since.as_ref().is_none_or(|since| package.last_updated() >= since.as_str()))
+            .collect();
+
+        // Kararlı sıralama: eşit değerlerde önceki sıra korunur
+        match self.sort {
+            SortOrder::Default => {}
+            SortOrder::Name => packages.sort_by(|a, b| a.name.cmp(&b.name)),
+            SortOrder::DownloadSize => packages.sort_by_key(|package| package.package_size),
+            SortOrder::InstalledSize => packages.sort_by_key(|package| package.installed_size),
+            SortOrder::LastUpdated => packages.sort_by(|a, b| a.last_updated().cmp(b.last_updated())),
+        }
+        if self.descending && self.sort != SortOrder::Default {
+            packages.reverse();
+        }
+        packages
+    }
+
+    /// Varsayılandan farklı filtreler, çubuktaki sırayla
+    pub fn chips(&self) -> Vec<FilterChip> {
+        let mut chips = Vec::new();
+        if self.state != InstallState::Any {
+            chips.push(FilterChip::State(self.state));
+        }
+        if let Some(license) = &self.license {
+            chips.push(FilterChip::License(license.clone()));
+        }
+        if let Some(architecture) = &self.architecture {
+            chips.push(FilterChip::Architecture(architecture.clone()));
+        }
+        if let Some(repository) = &self.repository {
+            chips.push(FilterChip::Repository(repository.clone()));
+        }
+        if !self.download_size.is_any() {
+            chips.push(FilterChip::DownloadSize(self.download_size));
+        }
+        if !self.installed_size.is_any() {
+            chips.push(FilterChip::InstalledSize(self.installed_size));
+        }
+        if self.updated_within != UpdatedWithin::Any {
+            chips.push(FilterChip::Updated(self.updated_within));
+        }
+        chips
+    }
+
+    /// Etiketin temsil ettiği filtreyi sıfırla
+    pub fn remove(&mut self, chip: &FilterChip) {
+        match chip {
+            FilterChip::State(_) => self.state = InstallState::Any,
+            FilterChip::License(_) => self.license = None,
+            FilterChip::Architecture(_) => self.architecture = None,
+            FilterChip::Repository(_) => self.repository = None,
+            FilterChip::DownloadSize(_) => self.download_size = SizeRange::ANY,
+            FilterChip::InstalledSize(_) => self.installed_size = SizeRange::ANY,
+            FilterChip::Updated(_) => self.updated_within = UpdatedWithin::Any,
+        }
+    }
+
+    /// Sıralama dışındaki tüm filtreleri kaldır
+    pub fn clear(&mut self) {
+        *self = Self {
+            sort: self.sort,
+            descending: self.descending,
+            ..Self::default()
+        };
+    }
+}
+
+/// Bugünden `days` gün önceki tarih, History ile karşılaştırmak için "YYYY-MM-DD"
+pub fn date_days_ago(days: u64) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let day_number = (now / 86_400).saturating_sub(days) as i64;
+
+    // Gün sayısından takvim tarihine (Howard Hinnant'ın civil_from_days algoritması)
+    let z = day_number + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
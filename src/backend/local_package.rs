@@ -0,0 +1,63 @@
+use anyhow::Result;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use crate::backend::xml_parser::{PackageFile, PackageInfo, XmlParser};
+
+/// Diskteki bir .pisi dosyası (zip arşivi: metadata.xml + files.xml + install.tar.xz)
+#[derive(Debug, Clone)]
+pub struct LocalPackage {
+    pub path: PathBuf,
+    pub info: PackageInfo,
+    pub files: Vec<PackageFile>,
+    pub replaces: Vec<String>,
+}
+
+impl LocalPackage {
+    pub fn is_pisi_file(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext == "pisi")
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        println!("Opening local package: {}", path.display());
+
+        let file = File::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path.display(), e))?;
+        let mut archive = zip::ZipArchive::new(file)
+            .map_err(|e| anyhow::anyhow!("{} is not a valid .pisi archive: {}", path.display(), e))?;
+
+        let metadata = Self::read_entry(&mut archive, "metadata.xml")?;
+        let (info, replaces) = XmlParser::parse_package_metadata(&metadata)?;
+
+        // files.xml eski paketlerde eksik olabilir, önizleme yine de gösterilsin
+        let files = match Self::read_entry(&mut archive, "files.xml") {
+            Ok(files_xml) => XmlParser::parse_files_xml(&files_xml)?,
+            Err(e) => {
+                println!("Warning: {}", e);
+                Vec::new()
+            }
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            info,
+            files,
+            replaces,
+        })
+    }
+
+    fn read_entry(archive: &mut zip::ZipArchive<File>, name: &str) -> Result<String> {
+        let mut entry = archive
+            .by_name(name)
+            .map_err(|e| anyhow::anyhow!("{} not found in package: {}", name, e))?;
+
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        Ok(content)
+    }
+
+    /// Kurulu dosyaların toplam boyutu
+    pub fn total_files_size(&self) -> u64 {
+        self.files.iter().map(|f| f.size).sum()
+    }
+}
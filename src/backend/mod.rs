@@ -1,7 +1,31 @@
 pub mod package_manager;
 pub mod xml_parser;
 pub mod repository;
+pub mod local_package;
+pub mod db_lock;
+pub mod downloader;
+pub mod privileged;
+pub mod pisi_conf;
+pub mod cache;
+pub mod search;
+pub mod search_index;
+pub mod provides;
+pub mod filter;
+pub mod collections;
+pub mod catalog;
 
 pub use package_manager::*;
 pub use xml_parser::*;
 pub use repository::*;
+pub use local_package::*;
+pub use db_lock::*;
+pub use downloader::*;
+pub use privileged::*;
+pub use pisi_conf::*;
+pub use cache::*;
+pub use search::*;
+pub use search_index::*;
+pub use provides::*;
+pub use filter::*;
+pub use collections::*;
+pub use catalog::*;
@@ -1,12 +1,67 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use anyhow::Result;
+use crate::backend::db_lock::DbLock;
+use crate::t;
+
+/// Kurulu paket veritabanı; her paket "<ad>-<sürüm>-<release>" adlı bir dizindir
+pub const INSTALLED_DB_DIR: &str = "/var/lib/pisi/package";
+
+/// Veritabanını değiştiren pisi işlemleri
+#[derive(Debug, Clone, PartialEq)]
+pub enum PackageOperation {
+    Install(String),
+    InstallLocal(PathBuf),
+    Remove(String),
+    Update(String),
+    UpdateAll { held: Vec<String> },
+}
+
+impl PackageOperation {
+    pub fn description(&self) -> String {
+        match self {
+            PackageOperation::Install(name) => t!("Install {}", name),
+            PackageOperation::InstallLocal(path) => t!("Install {}", path.display()),
+            PackageOperation::Remove(name) => t!("Remove {}", name),
+            PackageOperation::Update(name) => t!("Update {}", name),
+            PackageOperation::UpdateAll { .. } => t!("Update all packages"),
+        }
+    }
+
+    /// İşlemi çalıştır; veritabanı kilitliyse pisi'yi hiç başlatma
+    ///
+    /// `bandwidth_limit` (KB/s) pisi'nin paket indirmelerine uygulanır.
+    pub fn run(&self, bandwidth_limit: Option<u32>) -> Result<()> {
+        if let Some(holder) = DbLock::holder() {
+            return Err(anyhow::anyhow!("Pisi database is locked by {}", holder));
+        }
+
+        match self {
+            PackageOperation::Install(name) => PackageManager::install_package(name, bandwidth_limit),
+            PackageOperation::InstallLocal(path) => PackageManager::install_local_package(path),
+            PackageOperation::Remove(name) => PackageManager::remove_package(name),
+            PackageOperation::Update(name) => PackageManager::update_package(name, bandwidth_limit),
+            PackageOperation::UpdateAll { held } => PackageManager::update_all_packages(held, bandwidth_limit),
+        }
+    }
+}
 
 pub struct PackageManager;
 
 impl PackageManager {
-    pub fn install_package(package_name: &str) -> Result<()> {
+    /// İndirme yapan pisi komutları için bant genişliği sınırı
+    fn bandwidth_args(bandwidth_limit: Option<u32>) -> Vec<String> {
+        match bandwidth_limit {
+            Some(limit) => vec!["--bandwidth-limit".to_string(), limit.to_string()],
+            None => Vec::new(),
+        }
+    }
+
+    pub fn install_package(package_name: &str, bandwidth_limit: Option<u32>) -> Result<()> {
         let output = Command::new("pisi")
         .args(["install", "-y", package_name])
+        .args(Self::bandwidth_args(bandwidth_limit))
         .output()?;
 
         if output.status.success() {
@@ -18,6 +73,23 @@ impl PackageManager {
         }
     }
 
+    /// Diskteki bir .pisi dosyasını kur
+    pub fn install_local_package(path: &Path) -> Result<()> {
+        let output = Command::new("pisi")
+        .arg("install")
+        .arg("-y")
+        .arg(path)
+        .output()?;
+
+        if output.status.success() {
+            println!("Local package {} installed successfully", path.display());
+            Ok(())
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow::anyhow!("Failed to install {}: {}", path.display(), error))
+        }
+    }
+
     pub fn remove_package(package_name: &str) -> Result<()> {
         let output = Command::new("pisi")
         .args(["remove", "-y", package_name])
@@ -32,9 +104,10 @@ impl PackageManager {
         }
     }
 
-    pub fn update_package(package_name: &str) -> Result<()> {
+    pub fn update_package(package_name: &str, bandwidth_limit: Option<u32>) -> Result<()> {
         let output = Command::new("pisi")
         .args(["update", "-y", package_name])
+        .args(Self::bandwidth_args(bandwidth_limit))
         .output()?;
 
         if output.status.success() {
@@ -46,13 +119,42 @@ impl PackageManager {
         }
     }
 
-    pub fn update_all_packages() -> Result<()> {
+    /// Tüm paketleri güncelle; `held` listesindeki paketlere dokunulmaz
+    /// Bekleyen güncellemeleri kur; hold listesindekiler komuta verilmez
+    ///
+    /// pisi, güncellenen bir paket sabitlenmiş bir paketin daha yeni sürümünü
+    /// gerektiriyorsa onu da bağımlılık olarak yükseltir. Hold yalnızca paketin
+    /// kendi başına güncellenmesini engeller; ayarlarda da bu belirtilir.
+    pub fn update_all_packages(held: &[String], bandwidth_limit: Option<u32>) -> Result<()> {
+        if held.is_empty() {
+            let output = Command::new("pisi")
+            .args(["update", "-y"])
+            .args(Self::bandwidth_args(bandwidth_limit))
+            .output()?;
+
+            return if output.status.success() {
+                println!("All packages updated successfully");
+                Ok(())
+            } else {
+                let error = String::from_utf8_lossy(&output.stderr);
+                Err(anyhow::anyhow!("Failed to update packages: {}", error))
+            };
+        }
+
+        let upgradable = Self::filter_held(Self::list_upgradable_packages()?, held);
+        if upgradable.is_empty() {
+            println!("No packages to update (held: {})", held.join(", "));
+            return Ok(());
+        }
+
         let output = Command::new("pisi")
         .args(["update", "-y"])
+        .args(Self::bandwidth_args(bandwidth_limit))
+        .args(&upgradable)
         .output()?;
 
         if output.status.success() {
-            println!("All packages updated successfully");
+            println!("{} packages updated successfully, {} held", upgradable.len(), held.len());
             Ok(())
         } else {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -60,6 +162,42 @@ impl PackageManager {
         }
     }
 
+    /// Güncellemesi bekleyen paketlerin isimleri (`pisi list-upgrades`)
+    pub fn list_upgradable_packages() -> Result<Vec<String>> {
+        let output = Command::new("pisi")
+        .args(["list-upgrades"])
+        .output()?;
+
+        if output.status.success() {
+            Ok(Self::parse_upgrade_list(&String::from_utf8_lossy(&output.stdout)))
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow::anyhow!("Failed to list upgrades: {}", error))
+        }
+    }
+
+    /// Satırlar "isim - özet" ya da yalnızca "isim" biçimindedir; "No packages
+    /// to upgrade." gibi bilgi satırları atlanır
+    fn parse_upgrade_list(output: &str) -> Vec<String> {
+        output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let name = line.split_whitespace().next()?;
+            let is_entry = line == name || line[name.len()..].trim_start().starts_with("- ");
+            is_entry.then(|| name.to_string())
+        })
+        .collect()
+    }
+
+    /// Hold listesindeki paketleri çıkar
+    pub fn filter_held(packages: Vec<String>, held: &[String]) -> Vec<String> {
+        packages
+        .into_iter()
+        .filter(|name| !held.contains(name))
+        .collect()
+    }
+
     pub fn search_packages(query: &str) -> Result<Vec<String>> {
         let output = Command::new("pisi")
         .args(["search", query])
@@ -95,4 +233,58 @@ impl PackageManager {
             Err(anyhow::anyhow!("Failed to get installed packages: {}", error))
         }
     }
+
+    /// Kurulu paketlerin "sürüm-release" bilgisi, veritabanı dizin adlarından
+    pub fn get_installed_versions() -> Result<HashMap<String, String>> {
+        let entries = std::fs::read_dir(INSTALLED_DB_DIR)
+            .map_err(|e| anyhow::anyhow!("Could not read {}: {}", INSTALLED_DB_DIR, e))?;
+
+        Ok(entries
+            .flatten()
+            .filter_map(|entry| {
+                let directory = entry.file_name().to_string_lossy().to_string();
+                Self::split_installed_dir(&directory)
+                    .map(|(name, version)| (name.to_string(), version.to_string()))
+            })
+            .collect())
+    }
+
+    /// "firefox-esr-115.0-12" -> ("firefox-esr", "115.0-12")
+    pub fn split_installed_dir(directory: &str) -> Option<(&str, &str)> {
+        let mut parts = directory.rsplitn(3, '-');
+        let _release = parts.next()?;
+        let _version = parts.next()?;
+        let name = parts.next().filter(|name| !name.is_empty())?;
+        Some((name, &directory[name.len() + 1..]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_list_upgrades_output() {
+        let output = "firefox        - Mozilla Firefox web browser\nglibc - GNU C library\n\nkernel\n";
+
+        assert_eq!(PackageManager::parse_upgrade_list(output), names(&["firefox", "glibc", "kernel"]));
+    }
+
+    #[test]
+    fn skips_informational_lines() {
+        assert!(PackageManager::parse_upgrade_list("No packages to upgrade.\n").is_empty());
+        assert!(PackageManager::parse_upgrade_list("").is_empty());
+    }
+
+    #[test]
+    fn filters_held_packages() {
+        let upgradable = names(&["firefox", "glibc", "kernel"]);
+
+        assert_eq!(PackageManager::filter_held(upgradable.clone(), &names(&["kernel", "gimp"])), names(&["firefox", "glibc"]));
+        assert_eq!(PackageManager::filter_held(upgradable.clone(), &[]), upgradable);
+    }
 }
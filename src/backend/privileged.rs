@@ -0,0 +1,59 @@
+use anyhow::Result;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Root yetkisi gereken işlemler; root değilsek pkexec üzerinden çalıştırılır
+pub struct Privileged;
+
+impl Privileged {
+    pub fn is_root() -> bool {
+        // /proc/self/status: "Uid:\t<real>\t<effective>\t<saved>\t<fs>"
+        std::fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status
+            .lines()
+            .find(|line| line.starts_with("Uid:"))
+            .and_then(|line| line.split_whitespace().nth(2).map(|uid| uid == "0"))
+        })
+        .unwrap_or(false)
+    }
+
+    pub fn command(program: &str) -> Command {
+        if Self::is_root() {
+            Command::new(program)
+        } else {
+            let mut command = Command::new("pkexec");
+            command.arg(program);
+            command
+        }
+    }
+
+    /// İçeriği stdin üzerinden yetkili `install`'a ver; root'a kullanıcının
+    /// yazabildiği hiçbir dosya yolu verilmez. `mode` install'ın beklediği
+    /// sekizlik izinlerdir: "0644"
+    pub fn write_file(path: &Path, content: &str, mode: &str) -> Result<()> {
+        let mut child = Self::command("install")
+        .args(["-m", mode, "/dev/stdin"])
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+        // stdin kapanınca install kopyalamayı bitirir
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(content.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+
+        if output.status.success() {
+            println!("{} written successfully", path.display());
+            Ok(())
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow::anyhow!("Failed to write {}: {}", path.display(), error))
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use crate::backend::package_manager::{PackageManager, INSTALLED_DB_DIR};
+use crate::backend::search::*;
+use crate::backend::xml_parser::{PackageInfo, XmlParser};
+
+/// Dosya adının ortasında arama için en kısa sorgu; daha kısası her paketi getirir
+const MIN_CONTAINS_LENGTH: usize = 3;
+
+/// Bir paketin sorguyu karşılayan girdisi
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderHit {
+    pub name: String,
+    /// Eşleşen pkg-config adı ya da dosya yolu
+    pub provided: String,
+    pub score: u32,
+}
+
+/// Kurulu bir dosya; aramada her tuşta yeniden küçük harfe çevrilmesin diye
+/// küçük harfli kopyası yüklenirken hazırlanır
+#[derive(Debug, Clone)]
+struct InstalledFile {
+    path: String,
+    folded: String,
+    /// `InstalledFiles::packages` içindeki sıra
+    package: u32,
+}
+
+/// Kurulu paketlerin files.xml içerikleri
+#[derive(Debug, Clone, Default)]
+pub struct InstalledFiles {
+    packages: Vec<String>,
+    /// Küçük harfli yola göre sıralı; dizin önekleri ikili aramayla bulunur
+    files: Vec<InstalledFile>,
+    /// Küçük harfli dosya adı -> `files` sıraları; sıralı olduğu için önek araması yapılır
+    basenames: BTreeMap<String, Vec<u32>>,
+}
+
+impl InstalledFiles {
+    /// Tüm kurulu paketlerin dosya listelerini oku; okunamayanlar atlanır
+    pub fn load() -> Self {
+        let Ok(entries) = std::fs::read_dir(INSTALLED_DB_DIR) else {
+            println!("No installed package database at {}", INSTALLED_DB_DIR);
+            return Self::default();
+        };
+
+        let mut packages = Vec::new();
+        for entry in entries.flatten() {
+            let directory = entry.file_name().to_string_lossy().to_string();
+            let Some((name, _)) = PackageManager::split_installed_dir(&directory) else {
+                continue;
+            };
+            match Self::read_files(&entry.path().join("files.xml")) {
+                Ok(paths) => packages.push((name.to_string(), paths)),
+                Err(e) => println!("Skipping files of {}: {}", directory, e),
+            }
+        }
+
+        println!("Loaded file lists of {} installed packages", packages.len());
+        Self::from_packages(packages)
+    }
+
+    /// (paket adı, dosya yolları) listesinden arama yapılarını kur
+    pub fn from_packages(mut packages: Vec<(String, Vec<String>)>) -> Self {
+        packages.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut files = Vec::new();
+        let mut names = Vec::with_capacity(packages.len());
+        for (index, (name, paths)) in packages.into_iter().enumerate() {
+            names.push(name);
+            files.extend(paths.into_iter().map(|path| InstalledFile {
+                folded: path.to_lowercase(),
+                path,
+                package: index as u32,
+            }));
+        }
+        files.sort_by(|a, b| a.folded.cmp(&b.folded));
+
+        let mut basenames: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for (index, file) in files.iter().enumerate() {
+            let basename = file.folded.rsplit('/').next().unwrap_or(&file.folded);
+            basenames.entry(basename.to_string()).or_default().push(index as u32);
+        }
+
+        Self { packages: names, files, basenames }
+    }
+
+    fn read_files(path: &Path) -> anyhow::Result<Vec<String>> {
+        let content = std::fs::read_to_string(path)?;
+        let files = XmlParser::parse_files_xml(&content)?;
+        // files.xml yolları kök dizinsiz yazar: "usr/lib/libfoo.so.2"
+        Ok(files
+            .into_iter()
+            .map(|file| format!("/{}", file.path.trim_start_matches('/')))
+            .collect())
+    }
+
+    /// Sorguya uyan dosyalar ve puanları (bkz. `ProviderSearch::search`)
+    fn matches(&self, query: &str) -> Vec<(&InstalledFile, u32)> {
+        let mut matches = Vec::new();
+
+        if query.starts_with('/') {
+            // Tam yol ya da dizin: "/usr/bin/foo", "/usr/share/foo/"
+            let exact = query.trim_end_matches('/');
+            let start = self.files.partition_point(|file| file.folded.as_str() < query);
+            for file in self.files[start..].iter().take_while(|file| file.folded.starts_with(query)) {
+                let score = if file.folded == exact { SCORE_EXACT_NAME } else { SCORE_NAME_PREFIX };
+                matches.push((file, score));
+            }
+            if exact != query {
+                if let Ok(index) = self.files.binary_search_by(|file| file.folded.as_str().cmp(exact)) {
+                    matches.push((&self.files[index], SCORE_EXACT_NAME));
+                }
+            }
+            return matches;
+        }
+
+        // Dosya adı ve öneki: "libfoo.so" -> "libfoo.so.2.0.1"
+        for (basename, indexes) in self.basenames.range(query.to_string()..) {
+            if !basename.starts_with(query) {
+                break;
+            }
+            let score = if basename == query { SCORE_EXACT_NAME } else { SCORE_NAME_PREFIX };
+            matches.extend(indexes.iter().map(|&index| (&self.files[index as usize], score)));
+        }
+
+        // Yolun sonu: "bin/foo" -> "/usr/bin/foo"
+        if let Some((_, last)) = query.rsplit_once('/') {
+            for &index in self.basenames.get(last).into_iter().flatten() {
+                let file = &self.files[index as usize];
+                if file.folded.strip_suffix(query).is_some_and(|parent| parent.ends_with('/')) {
+                    matches.push((file, SCORE_NAME_WORD));
+                }
+            }
+        }
+
+        // Dosya adının ortasında: "foo" -> "libfoo.so.2"; tam yollar taranmaz,
+        // yalnızca tekil dosya adlarına bakılır
+        if query.len() >= MIN_CONTAINS_LENGTH && !query.contains('/') {
+            for (basename, indexes) in &self.basenames {
+                if basename.contains(query) && !basename.starts_with(query) {
+                    matches.extend(indexes.iter().map(|&index| (&self.files[index as usize], SCORE_NAME_CONTAINS)));
+                }
+            }
+        }
+        matches
+    }
+}
+
+pub struct ProviderSearch;
+
+impl ProviderSearch {
+    /// Sorguyu sağlayan paketler: index'teki pkg-config adları ve kurulu dosyalar
+    ///
+    /// "foo.pc", "foo", "libfoo.so.2", "libfoo.so" ya da "/usr/bin/foo" yazılabilir.
+    /// Kurulu olmayan paketlerin dosya listesi bilinmediği için onlarda yalnızca
+    /// index'teki <Provides> kayıtlarına bakılır.
+    pub fn search(packages: &[PackageInfo], installed: &InstalledFiles, query: &str) -> Vec<ProviderHit> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut best: HashMap<&str, (u32, String)> = HashMap::new();
+        let pkgconfig = query.strip_suffix(".pc").unwrap_or(&query);
+        for package in packages {
+            for provide in &package.provides {
+                let Some(score) = Self::name_score(&provide.name, pkgconfig) else {
+                    continue;
+                };
+                Self::keep_best(&mut best, &package.name, score, || {
+                    if provide.kind.starts_with("PkgConfig") {
+                        format!("{}.pc", provide.name)
+                    } else {
+                        provide.name.clone()
+                    }
+                });
+            }
+        }
+
+        for (file, score) in installed.matches(&query) {
+            let name = &installed.packages[file.package as usize];
+            Self::keep_best(&mut best, name, score, || file.path.clone());
+        }
+
+        let mut hits: Vec<ProviderHit> = best
+            .into_iter()
+            .map(|(name, (score, provided))| ProviderHit { name: name.to_string(), provided, score })
+            .collect();
+        hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+        hits
+    }
+
+    /// Girdi metni yalnızca daha iyi bir eşleşme bulununca oluşturulur
+    fn keep_best<'a>(
+        best: &mut HashMap<&'a str, (u32, String)>,
+        name: &'a str,
+        score: u32,
+        provided: impl FnOnce() -> String,
+    ) {
+        match best.get(name) {
+            Some((previous, _)) if *previous >= score => {}
+            _ => {
+                best.insert(name, (score, provided()));
+            }
+        }
+    }
+
+    /// pkg-config adları ASCII'dir; büyük/küçük harf ayrımı kopyalanmadan yok sayılır
+    fn name_score(name: &str, query: &str) -> Option<u32> {
+        let name = name.as_bytes();
+        let query = query.as_bytes();
+        let starts_with = name.len() >= query.len() && name[..query.len()].eq_ignore_ascii_case(query);
+        if starts_with && name.len() == query.len() {
+            Some(SCORE_EXACT_NAME)
+        } else if starts_with {
+            Some(SCORE_NAME_PREFIX)
+        } else if query.len() >= MIN_CONTAINS_LENGTH
+            && name.windows(query.len()).any(|window| window.eq_ignore_ascii_case(query))
+        {
+            Some(SCORE_NAME_CONTAINS)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::xml_parser::Provide;
+
+    fn installed() -> InstalledFiles {
+        let files = |paths: &[&str]| paths.iter().map(|path| path.to_string()).collect();
+        InstalledFiles::from_packages(vec![
+            ("libfoo".to_string(), files(&["/usr/lib/libfoo.so.2", "/usr/lib/libfoo.so.2.0.1", "/usr/share/foo/data.xml"])),
+            ("foo".to_string(), files(&["/usr/bin/foo", "/usr/share/man/man1/foo.1"])),
+            ("barfoo-tools".to_string(), files(&["/usr/bin/barfoo", "/usr/sbin/foo"])),
+        ])
+    }
+
+    fn hits(packages: &[PackageInfo], query: &str) -> Vec<(String, String, u32)> {
+        ProviderSearch::search(packages, &installed(), query)
+            .into_iter()
+            .map(|hit| (hit.name, hit.provided, hit.score))
+            .collect()
+    }
+
+    fn hit(name: &str, provided: &str, score: u32) -> (String, String, u32) {
+        (name.to_string(), provided.to_string(), score)
+    }
+
+    #[test]
+    fn matches_full_paths_and_directories() {
+        assert_eq!(hits(&[], "/usr/bin/foo"), [hit("foo", "/usr/bin/foo", SCORE_EXACT_NAME)]);
+        assert_eq!(hits(&[], "/usr/share/foo/"), [hit("libfoo", "/usr/share/foo/data.xml", SCORE_NAME_PREFIX)]);
+        assert!(hits(&[], "/opt/").is_empty());
+    }
+
+    #[test]
+    fn matches_file_names_and_path_ends() {
+        assert_eq!(hits(&[], "libfoo.so"), [hit("libfoo", "/usr/lib/libfoo.so.2", SCORE_NAME_PREFIX)]);
+        assert_eq!(hits(&[], "LIBFOO.SO.2"), [hit("libfoo", "/usr/lib/libfoo.so.2", SCORE_EXACT_NAME)]);
+        assert_eq!(hits(&[], "sbin/foo"), [hit("barfoo-tools", "/usr/sbin/foo", SCORE_NAME_WORD)]);
+        // Yol sonu dizin sınırında başlamalı
+        assert!(hits(&[], "in/foo").is_empty());
+    }
+
+    #[test]
+    fn contains_search_looks_only_at_file_names() {
+        assert_eq!(
+            hits(&[], "foo"),
+            [
+                hit("barfoo-tools", "/usr/sbin/foo", SCORE_EXACT_NAME),
+                hit("foo", "/usr/bin/foo", SCORE_EXACT_NAME),
+                hit("libfoo", "/usr/lib/libfoo.so.2", SCORE_NAME_CONTAINS),
+            ]
+        );
+        // Yalnızca dizin adında geçen sorgu eşleşmez
+        assert!(hits(&[], "share").is_empty());
+        assert!(hits(&[], "man1").is_empty());
+        // Kısa sorgular dosya adının ortasında aranmaz
+        assert!(hits(&[], "oo").is_empty());
+    }
+
+    #[test]
+    fn matches_pkgconfig_names_from_index() {
+        let mut gtk = PackageInfo::for_test("gtk3", "GTK toolkit", "");
+        gtk.provides = vec![Provide { kind: "PkgConfig".to_string(), name: "gtk+-3.0".to_string() }];
+        let packages = [gtk];
+
+        assert_eq!(hits(&packages, "gtk+-3.0.pc"), [hit("gtk3", "gtk+-3.0.pc", SCORE_EXACT_NAME)]);
+        assert_eq!(hits(&packages, "GTK+"), [hit("gtk3", "gtk+-3.0.pc", SCORE_NAME_PREFIX)]);
+        assert_eq!(hits(&packages, "3.0"), [hit("gtk3", "gtk+-3.0.pc", SCORE_NAME_CONTAINS)]);
+        assert!(hits(&packages, "  ").is_empty());
+    }
+}
@@ -1,11 +1,70 @@
 use anyhow::Result;
 use std::process::Command;
+use crate::config::Repository;
+use crate::backend::downloader::{DownloadOutcome, Downloader};
+use crate::backend::privileged::Privileged;
+use crate::config::paths;
+use crate::backend::xml_parser::{IndexSummary, XmlParser};
+use crate::t;
 
+/// Depo listesini değiştiren işlemler; pisi index indirebildiği ve yetki
+/// sorabildiği için arka planda çalıştırılır
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepoOperation {
+    Enable(String),
+    Disable(String),
+    Remove(String),
+    /// Depoyu `from` sırasından `to` sırasına taşı (0 = en yüksek öncelik)
+    Move { repo: Repository, from: usize, to: usize },
+}
+
+impl RepoOperation {
+    pub fn description(&self) -> String {
+        match self {
+            RepoOperation::Enable(name) => t!("Enable repository {}", name),
+            RepoOperation::Disable(name) => t!("Disable repository {}", name),
+            RepoOperation::Remove(name) => t!("Remove repository {}", name),
+            RepoOperation::Move { repo, .. } => t!("Move repository {}", repo.name),
+        }
+    }
+
+    pub fn run(&self) -> Result<()> {
+        match self {
+            RepoOperation::Enable(name) => RepositoryManager::enable_repository(name),
+            RepoOperation::Disable(name) => RepositoryManager::disable_repository(name),
+            RepoOperation::Remove(name) => RepositoryManager::remove_repository(name),
+            RepoOperation::Move { repo, from, to } => RepositoryManager::move_repository(repo, *from, *to),
+        }
+    }
+
+    /// Başarılı işlemin sonucunu listeye uygula; sonraki eşitleme bunu
+    /// dışarıdan yapılmış bir değişiklik sanmasın
+    pub fn apply(&self, repositories: &mut Vec<Repository>) {
+        match self {
+            RepoOperation::Enable(name) | RepoOperation::Disable(name) => {
+                let enabled = matches!(self, RepoOperation::Enable(_));
+                if let Some(repo) = repositories.iter_mut().find(|repo| &repo.name == name) {
+                    repo.enabled = enabled;
+                }
+            }
+            RepoOperation::Remove(name) => repositories.retain(|repo| &repo.name != name),
+            RepoOperation::Move { repo, to, .. } => {
+                if let Some(index) = repositories.iter().position(|r| r.name == repo.name) {
+                    let moved = repositories.remove(index);
+                    repositories.insert((*to).min(repositories.len()), moved);
+                }
+            }
+        }
+    }
+}
+
+/// Depo listesini değiştiren komutlar `Privileged::command` ile root olarak
+/// çalışır; yalnızca okuyan `list-repo` normal kullanıcıyla çalışır
 pub struct RepositoryManager;
 
 impl RepositoryManager {
     pub fn add_repository(name: &str, url: &str) -> Result<()> {
-        let output = Command::new("pisi")
+        let output = Privileged::command("pisi")
         .args(["add-repo", name, url])
         .output()?;
 
@@ -18,8 +77,53 @@ impl RepositoryManager {
         }
     }
 
+    /// Depoyu verilen öncelik sırasına ekle (0 = en yüksek öncelik)
+    pub fn add_repository_at(name: &str, url: &str, position: usize) -> Result<()> {
+        let output = Privileged::command("pisi")
+        .args(["add-repo", "--at", &position.to_string(), name, url])
+        .output()?;
+
+        if output.status.success() {
+            println!("Repository {} added at position {}", name, position);
+            Ok(())
+        } else {
+            let error = String::from_utf8_lossy(&output.stderr);
+            Err(anyhow::anyhow!("Failed to add repository: {}", error))
+        }
+    }
+
+    /// Depoyu silip yeni konumda tekrar ekleyerek önceliğini değiştir
+    pub fn move_repository(repo: &Repository, from: usize, to: usize) -> Result<()> {
+        if repo.url.is_empty() {
+            return Err(anyhow::anyhow!("URL of repository {} is unknown, cannot reorder", repo.name));
+        }
+
+        Self::remove_repository(&repo.name)?;
+        let result = Self::add_repository_at(&repo.name, &repo.url, to);
+        if let Err(e) = result {
+            // Depoyu kaybetmemek için eski yerine geri koy
+            if let Err(restore) = Self::add_repository_at(&repo.name, &repo.url, from) {
+                return Err(anyhow::anyhow!(
+                    "Repository {} was removed and could not be added back ({}): {}. Add it again with URL {}",
+                    repo.name, restore, e, repo.url
+                ));
+            }
+            Self::restore_disabled(repo)?;
+            return Err(e);
+        }
+        Self::restore_disabled(repo)
+    }
+
+    /// add-repo depoyu etkin olarak ekler, eski durumu koru
+    fn restore_disabled(repo: &Repository) -> Result<()> {
+        if !repo.enabled {
+            Self::disable_repository(&repo.name)?;
+        }
+        Ok(())
+    }
+
     pub fn remove_repository(name: &str) -> Result<()> {
-        let output = Command::new("pisi")
+        let output = Privileged::command("pisi")
         .args(["remove-repo", name])
         .output()?;
 
@@ -33,7 +137,7 @@ impl RepositoryManager {
     }
 
     pub fn enable_repository(name: &str) -> Result<()> {
-        let output = Command::new("pisi")
+        let output = Privileged::command("pisi")
         .args(["enable-repo", name])
         .output()?;
 
@@ -47,7 +151,7 @@ impl RepositoryManager {
     }
 
     pub fn disable_repository(name: &str) -> Result<()> {
-        let output = Command::new("pisi")
+        let output = Privileged::command("pisi")
         .args(["disable-repo", name])
         .output()?;
 
@@ -61,7 +165,7 @@ impl RepositoryManager {
     }
 
     pub fn update_repositories() -> Result<()> {
-        let output = Command::new("pisi")
+        let output = Privileged::command("pisi")
         .args(["update-repo"])
         .output()?;
 
@@ -74,31 +178,289 @@ impl RepositoryManager {
         }
     }
 
-    pub fn list_repositories() -> Result<Vec<(String, bool)>> {
+    /// `pisi list-repo` çıktısını oku
+    ///
+    /// Pisi her depoyu "isim [active]" satırı ve altında girintili URL ile listeler.
+    pub fn list_repositories() -> Result<Vec<Repository>> {
         let output = Command::new("pisi")
         .args(["list-repo"])
         .output()?;
 
         if output.status.success() {
             let output_str = String::from_utf8_lossy(&output.stdout);
-            let repos: Vec<(String, bool)> = output_str
-            .lines()
-            .skip(1) // Skip header
-            .filter_map(|line| {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let name = parts[0].to_string();
-                    let enabled = parts[1] == "enabled";
-                    Some((name, enabled))
-                } else {
-                    None
-                }
-            })
-            .collect();
-            Ok(repos)
+            Ok(Self::parse_repo_list(&output_str))
         } else {
             let error = String::from_utf8_lossy(&output.stderr);
             Err(anyhow::anyhow!("Failed to list repositories: {}", error))
         }
     }
+
+    fn parse_repo_list(output: &str) -> Vec<Repository> {
+        let mut repos: Vec<Repository> = Vec::new();
+
+        for line in output.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // Girintili satır bir önceki deponun URL'si
+            if line.starts_with(char::is_whitespace) {
+                if let Some(repo) = repos.last_mut() {
+                    repo.url = line.trim().to_string();
+                }
+                continue;
+            }
+
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let Some(name) = parts.first() else {
+                continue;
+            };
+            let enabled = match parts.get(1) {
+                Some(&"[active]") | Some(&"enabled") => true,
+                Some(&"[inactive]") | Some(&"disabled") => false,
+                _ => continue,
+            };
+
+            repos.push(Repository {
+                enabled,
+                name: name.to_string(),
+                url: String::new(),
+            });
+        }
+
+        repos
+    }
+
+    /// Yeni depo için isim ve URL kontrolü
+    pub fn validate_repository(name: &str, url: &str, existing: &[Repository]) -> Result<()> {
+        if name.trim().is_empty() {
+            return Err(anyhow::anyhow!("Repository name is required"));
+        }
+        if name.chars().any(char::is_whitespace) {
+            return Err(anyhow::anyhow!("Repository name cannot contain spaces"));
+        }
+        if existing.iter().any(|r| r.name == name) {
+            return Err(anyhow::anyhow!("A repository named {} already exists", name));
+        }
+        if existing.iter().any(|r| r.url == url) {
+            return Err(anyhow::anyhow!("This index is already added"));
+        }
+
+        let is_remote = url.starts_with("http://") || url.starts_with("https://");
+        let is_local = url.starts_with("file://") || url.starts_with('/');
+        if !is_remote && !is_local {
+            return Err(anyhow::anyhow!("URL must start with http://, https://, file:// or /"));
+        }
+        if is_remote && url.split("://").nth(1).is_none_or(|host| host.split('/').next().unwrap_or("").is_empty()) {
+            return Err(anyhow::anyhow!("URL has no host"));
+        }
+        if !url.ends_with("pisi-index.xml") && !url.ends_with("pisi-index.xml.xz") {
+            return Err(anyhow::anyhow!("URL must point to pisi-index.xml or pisi-index.xml.xz"));
+        }
+        if is_local {
+            let path = url.strip_prefix("file://").unwrap_or(url);
+            if !std::path::Path::new(path).exists() {
+                return Err(anyhow::anyhow!("{} does not exist", path));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Index'i indirip parse et, depoyu eklemeden önce deneme amaçlı
+    pub fn test_repository(url: &str, downloader: &Downloader) -> Result<IndexSummary> {
+        let bytes = downloader.fetch(url)?;
+        let xml_content = XmlParser::decode_index(&bytes, url)?;
+        XmlParser::parse_index_summary(&xml_content)
+    }
+
+    /// Depo index'ini pisi'ye gerek kalmadan uygulamanın önbelleğine indir
+    pub fn refresh_index(repo: &Repository, downloader: &Downloader) -> Result<DownloadOutcome> {
+        let repo_dir = paths::index_cache_dir().join(&repo.name);
+        let file_name = repo.url.rsplit('/').next().unwrap_or("pisi-index.xml");
+        let download_path = repo_dir.join(file_name);
+        let index_path = repo_dir.join("pisi-index.xml");
+
+        let outcome = downloader.download_to(&repo.url, &download_path)?;
+
+        // Sıkıştırılmış index'i aç; 304 gelse bile açılmış kopya yoksa oluştur
+        if outcome != DownloadOutcome::NotModified || !index_path.exists() {
+            let bytes = std::fs::read(&download_path)?;
+            let xml_content = XmlParser::decode_index(&bytes, &repo.url)?;
+            std::fs::write(&index_path, xml_content)?;
+        }
+
+        Ok(outcome)
+    }
+
+    /// Uygulamadaki liste ile pisi'nin gerçek depoları arasındaki farklar
+    pub fn find_drift(local: &[Repository], actual: &[Repository]) -> Vec<RepoDrift> {
+        let mut drift = Vec::new();
+
+        for repo in actual {
+            match local.iter().find(|r| r.name == repo.name) {
+                None => drift.push(RepoDrift::Added(repo.name.clone())),
+                Some(known) => {
+                    if known.enabled != repo.enabled {
+                        drift.push(RepoDrift::EnabledChanged(repo.name.clone(), repo.enabled));
+                    }
+                    if !repo.url.is_empty() && known.url != repo.url {
+                        drift.push(RepoDrift::UrlChanged(repo.name.clone(), repo.url.clone()));
+                    }
+                }
+            }
+        }
+
+        for repo in local {
+            if !actual.iter().any(|r| r.name == repo.name) {
+                drift.push(RepoDrift::Removed(repo.name.clone()));
+            }
+        }
+
+        drift
+    }
+}
+
+/// Pisi CLI ile değiştirilmiş depolar
+#[derive(Debug, Clone, PartialEq)]
+pub enum RepoDrift {
+    Added(String),
+    Removed(String),
+    EnabledChanged(String, bool),
+    UrlChanged(String, String),
+}
+
+impl std::fmt::Display for RepoDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RepoDrift::Added(name) => write!(f, "Repository {} was added outside the app", name),
+            RepoDrift::Removed(name) => write!(f, "Repository {} was removed outside the app", name),
+            RepoDrift::EnabledChanged(name, true) => write!(f, "Repository {} was enabled outside the app", name),
+            RepoDrift::EnabledChanged(name, false) => write!(f, "Repository {} was disabled outside the app", name),
+            RepoDrift::UrlChanged(name, url) => write!(f, "Repository {} now points to {}", name, url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(name: &str, enabled: bool, url: &str) -> Repository {
+        Repository { enabled, name: name.to_string(), url: url.to_string() }
+    }
+
+    #[test]
+    fn parses_list_repo_output() {
+        let output = "pisilinux-2.0 [active]\n    https://ciftlik.pisilinux.org/2.0/pisi-index.xml.xz\n\
+                      contrib [inactive]\n    https://contrib.pisilinux.org/pisi-index.xml.xz\n\n\
+                      local enabled\n\tfile:///srv/repo/pisi-index.xml\n";
+
+        assert_eq!(RepositoryManager::parse_repo_list(output), vec![
+            repo("pisilinux-2.0", true, "https://ciftlik.pisilinux.org/2.0/pisi-index.xml.xz"),
+            repo("contrib", false, "https://contrib.pisilinux.org/pisi-index.xml.xz"),
+            repo("local", true, "file:///srv/repo/pisi-index.xml"),
+        ]);
+    }
+
+    #[test]
+    fn skips_unknown_lines_and_orphan_urls() {
+        let output = "    https://orphan.example.org/pisi-index.xml\nNo repositories found\nstable [active]\n";
+
+        assert_eq!(RepositoryManager::parse_repo_list(output), vec![repo("stable", true, "")]);
+    }
+
+    #[test]
+    fn finds_no_drift_for_same_list() {
+        let list = vec![repo("stable", true, "https://a/pisi-index.xml"), repo("contrib", false, "https://b/pisi-index.xml")];
+
+        assert!(RepositoryManager::find_drift(&list, &list).is_empty());
+    }
+
+    #[test]
+    fn finds_added_removed_toggled_and_moved_url() {
+        let local = vec![
+            repo("stable", true, "https://a/pisi-index.xml"),
+            repo("contrib", true, "https://b/pisi-index.xml"),
+            repo("old", true, "https://c/pisi-index.xml"),
+        ];
+        let actual = vec![
+            repo("stable", true, "https://mirror/pisi-index.xml"),
+            repo("contrib", false, "https://b/pisi-index.xml"),
+            repo("new", true, "https://d/pisi-index.xml"),
+        ];
+
+        assert_eq!(RepositoryManager::find_drift(&local, &actual), vec![
+            RepoDrift::UrlChanged("stable".to_string(), "https://mirror/pisi-index.xml".to_string()),
+            RepoDrift::EnabledChanged("contrib".to_string(), false),
+            RepoDrift::Added("new".to_string()),
+            RepoDrift::Removed("old".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn ignores_order_and_unknown_urls() {
+        let local = vec![repo("stable", true, "https://a/pisi-index.xml"), repo("contrib", true, "https://b/pisi-index.xml")];
+        let actual = vec![repo("contrib", true, ""), repo("stable", true, "https://a/pisi-index.xml")];
+
+        assert!(RepositoryManager::find_drift(&local, &actual).is_empty());
+    }
+
+    fn existing() -> Vec<Repository> {
+        vec![repo("stable", true, "https://stable.example.org/pisi-index.xml.xz")]
+    }
+
+    #[test]
+    fn accepts_new_remote_repository() {
+        let result = RepositoryManager::validate_repository("contrib", "https://contrib.example.org/pisi-index.xml.xz", &existing());
+        assert!(result.is_ok(), "{:?}", result);
+    }
+
+    #[test]
+    fn rejects_duplicate_name_and_url() {
+        let duplicate_name = RepositoryManager::validate_repository("stable", "https://other.example.org/pisi-index.xml", &existing());
+        assert!(duplicate_name.unwrap_err().to_string().contains("already exists"));
+
+        let duplicate_url = RepositoryManager::validate_repository("mirror", "https://stable.example.org/pisi-index.xml.xz", &existing());
+        assert!(duplicate_url.unwrap_err().to_string().contains("already added"));
+    }
+
+    #[test]
+    fn rejects_bad_names_schemes_and_targets() {
+        let invalid = [
+            ("", "https://a.example.org/pisi-index.xml"),
+            ("my repo", "https://a.example.org/pisi-index.xml"),
+            ("ftp", "ftp://a.example.org/pisi-index.xml"),
+            ("relative", "repo/pisi-index.xml"),
+            ("nohost", "https:///pisi-index.xml"),
+            ("page", "https://a.example.org/index.html"),
+        ];
+        for (name, url) in invalid {
+            assert!(RepositoryManager::validate_repository(name, url, &existing()).is_err(), "{} {}", name, url);
+        }
+    }
+
+    #[test]
+    fn checks_local_paths_exist() {
+        let dir = std::env::temp_dir().join(format!("pisi-repo-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let index = dir.join("pisi-index.xml");
+        std::fs::write(&index, "<PISI/>").unwrap();
+
+        let path = index.display().to_string();
+        assert!(RepositoryManager::validate_repository("local", &path, &existing()).is_ok());
+        assert!(RepositoryManager::validate_repository("local", &format!("file://{}", path), &existing()).is_ok());
+
+        let missing = dir.join("missing").join("pisi-index.xml").display().to_string();
+        assert!(RepositoryManager::validate_repository("local", &missing, &existing()).unwrap_err().to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn applies_move_to_local_list() {
+        let mut list = vec![repo("a", true, ""), repo("b", true, ""), repo("c", true, "")];
+        RepoOperation::Move { repo: list[2].clone(), from: 2, to: 0 }.apply(&mut list);
+
+        let names: Vec<&str> = list.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["c", "a", "b"]);
+    }
 }
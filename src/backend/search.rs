@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+
+/// Eşleşme türleri, yüksek puan önce gelir
+pub const SCORE_EXACT_NAME: u32 = 1000;
+pub const SCORE_NAME_PREFIX: u32 = 800;
+pub const SCORE_NAME_WORD: u32 = 700;
+pub const SCORE_NAME_CONTAINS: u32 = 600;
+pub const SCORE_FUZZY_NAME: u32 = 400;
+pub const SCORE_SUMMARY: u32 = 300;
+pub const SCORE_DESCRIPTION: u32 = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub name: String,
+    pub score: u32,
+}
+
+/// Arama kutusunun neye baktığı
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SearchMode {
+    /// Ad, özet ve açıklama
+    #[default]
+    Names,
+    /// pkg-config adları ve kurulu dosya yolları
+    Provides,
+}
+
+pub struct PackageSearch;
+
+impl PackageSearch {
+    /// Harf değiştirme, ekleme, silme ve yer değiştirme (Damerau) uzaklığı;
+    /// `limit`'i aşarsa None
+    pub fn edit_distance(a: &str, b: &str, limit: u32) -> Option<u32> {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        if a.len().abs_diff(b.len()) > limit as usize {
+            return None;
+        }
+
+        let mut previous2 = vec![0u32; b.len() + 1];
+        let mut previous: Vec<u32> = (0..=b.len() as u32).collect();
+        let mut current = vec![0u32; b.len() + 1];
+
+        for i in 1..=a.len() {
+            current[0] = i as u32;
+            let mut row_min = current[0];
+            for j in 1..=b.len() {
+                let cost = u32::from(a[i - 1] != b[j - 1]);
+                current[j] = (previous[j] + 1)
+                    .min(current[j - 1] + 1)
+                    .min(previous[j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    current[j] = current[j].min(previous2[j - 2] + 1);
+                }
+                row_min = row_min.min(current[j]);
+            }
+            if row_min > limit {
+                return None;
+            }
+            std::mem::swap(&mut previous2, &mut previous);
+            std::mem::swap(&mut previous, &mut current);
+        }
+
+        Some(previous[b.len()]).filter(|&distance| distance <= limit)
+    }
+
+    /// Terim uzunluğuna göre izin verilen yazım hatası sayısı
+    ///
+    /// Kısa terimlerde bulanık eşleşme çok fazla sonuç getirir.
+    pub fn allowed_typos(term: &str) -> Option<u32> {
+        match term.chars().count() {
+            0..=3 => None,
+            4..=6 => Some(1),
+            _ => Some(2),
+        }
+    }
+
+    /// Katlanmış (bkz. `fold`) kelimeler
+    pub fn tokenize(text: &str) -> Vec<String> {
+        fold(text)
+            .split(|c: char| !c.is_alphanumeric() && c != '+')
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Arama için harf katlama: küçük harf, Türkçe I/İ/ı/i ayrımı ve aksanlar kaldırılır
+///
+/// "IŞIK", "ışık" ve "isik" aynı sonucu verir; `to_lowercase` tek başına
+/// 'I'yı 'i'ye, 'İ'yi "i̇"ye çevirdiği için yeterli değildir.
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    for c in text.chars() {
+        let mapped = match c {
+            'I' | 'İ' | 'ı' | 'Ì' | 'Í' | 'Î' | 'Ï' | 'ì' | 'í' | 'î' | 'ï' => 'i',
+            'Ç' | 'ç' => 'c',
+            'Ğ' | 'ğ' => 'g',
+            'Ö' | 'ö' | 'Ò' | 'Ó' | 'Ô' | 'Õ' | 'ò' | 'ó' | 'ô' | 'õ' => 'o',
+            'Ş' | 'ş' => 's',
+            'Ü' | 'ü' | 'Ù' | 'Ú' | 'Û' | 'ù' | 'ú' | 'û' => 'u',
+            'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'È' | 'É' | 'Ê' | 'Ë' | 'è' | 'é' | 'ê' | 'ë' => 'e',
+            'Ñ' | 'ñ' => 'n',
+            'Ý' | 'ý' | 'ÿ' => 'y',
+            // Birleşik aksan işaretleri
+            '\u{0300}'..='\u{036F}' => continue,
+            _ => {
+                folded.extend(c.to_lowercase());
+                continue;
+            }
+        };
+        folded.push(mapped);
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::search_index::SearchIndex;
+    use crate::backend::xml_parser::PackageInfo;
+
+    #[test]
+    fn scores_rank_name_matches_above_text_matches() {
+        let order = [
+            SCORE_EXACT_NAME,
+            SCORE_NAME_PREFIX,
+            SCORE_NAME_WORD,
+            SCORE_NAME_CONTAINS,
+            SCORE_FUZZY_NAME,
+            SCORE_SUMMARY,
+            SCORE_DESCRIPTION,
+        ];
+        assert!(order.windows(2).all(|pair| pair[0] > pair[1]));
+    }
+
+    #[test]
+    fn ranks_hits_by_match_kind_then_name_length() {
+        let packages = [
+            PackageInfo::for_test("libreoffice", "Office suite", ""),
+            PackageInfo::for_test("office-themes", "Themes", ""),
+            PackageInfo::for_test("abiword", "Word processor", "Lightweight office application"),
+            PackageInfo::for_test("gnumeric", "Spreadsheet for the office", ""),
+            PackageInfo::for_test("office", "Meta package", ""),
+            PackageInfo::for_test("ofice-tools", "Misspelled tools", ""),
+            PackageInfo::for_test("calligra", "Suite", "Includes an office"),
+        ];
+        let index = SearchIndex::load_or_build(&packages, &[]);
+
+        let hits: Vec<(String, u32)> = index.search("office").into_iter().map(|hit| (hit.name, hit.score)).collect();
+        assert_eq!(
+            hits,
+            [
+                ("office".to_string(), SCORE_EXACT_NAME),
+                ("office-themes".to_string(), SCORE_NAME_PREFIX),
+                ("libreoffice".to_string(), SCORE_NAME_CONTAINS),
+                // Eşit puanda kısa ad önce
+                ("gnumeric".to_string(), SCORE_SUMMARY),
+                ("ofice-tools".to_string(), SCORE_FUZZY_NAME - 100),
+                ("abiword".to_string(), SCORE_DESCRIPTION),
+                ("calligra".to_string(), SCORE_DESCRIPTION),
+            ]
+        );
+    }
+
+    #[test]
+    fn edit_distance_counts_damerau_operations() {
+        assert_eq!(PackageSearch::edit_distance("firefox", "firefox", 2), Some(0));
+        assert_eq!(PackageSearch::edit_distance("firefox", "firefix", 2), Some(1));
+        assert_eq!(PackageSearch::edit_distance("firefox", "firefo", 2), Some(1));
+        assert_eq!(PackageSearch::edit_distance("firefox", "fireffox", 2), Some(1));
+        // Yan yana iki harfin yer değiştirmesi tek hata sayılır
+        assert_eq!(PackageSearch::edit_distance("firefox", "friefox", 2), Some(1));
+        assert_eq!(PackageSearch::edit_distance("gimp", "gpmi", 2), Some(2));
+    }
+
+    #[test]
+    fn edit_distance_stops_at_limit() {
+        assert_eq!(PackageSearch::edit_distance("firefox", "thunderbird", 2), None);
+        assert_eq!(PackageSearch::edit_distance("vlc", "vlc-player", 2), None);
+        assert_eq!(PackageSearch::edit_distance("kate", "kdev", 1), None);
+        assert_eq!(PackageSearch::edit_distance("", "ab", 2), Some(2));
+    }
+
+    #[test]
+    fn allows_more_typos_for_longer_terms() {
+        assert_eq!(PackageSearch::allowed_typos("vlc"), None);
+        assert_eq!(PackageSearch::allowed_typos("gimp"), Some(1));
+        assert_eq!(PackageSearch::allowed_typos("inkscape"), Some(2));
+    }
+
+    #[test]
+    fn folds_turkish_dotted_and_dotless_i() {
+        assert_eq!(fold("IŞIK"), "isik");
+        assert_eq!(fold("ışık"), "isik");
+        assert_eq!(fold("İstanbul"), "istanbul");
+        assert_eq!(fold("ılık Işık İnce ince"), "ilik isik ince ince");
+        // Birleşik nokta işareti ("i̇") atılır
+        assert_eq!(fold("i\u{0307}"), "i");
+    }
+
+    #[test]
+    fn folds_accents_and_case() {
+        assert_eq!(fold("Çağrı Gönüllü"), "cagri gonullu");
+        assert_eq!(fold("Éléphant Ñandú"), "elephant nandu");
+        assert_eq!(fold("e\u{0301}"), "e");
+        assert_eq!(fold("LibreOffice"), "libreoffice");
+    }
+
+    #[test]
+    fn tokenizes_folded_words() {
+        assert_eq!(PackageSearch::tokenize("GTK+ Ağ-Yöneticisi, 3.0"), ["gtk+", "ag", "yoneticisi", "3", "0"]);
+        assert!(PackageSearch::tokenize(" -- ").is_empty());
+    }
+}
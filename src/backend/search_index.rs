@@ -0,0 +1,490 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use crate::backend::search::*;
+use crate::backend::xml_parser::{PackageInfo, XmlParser};
+use crate::config::paths;
+
+/// Dosya biçimi değişirse artırılır, eski dosyalar yeniden oluşturulur
+const SEARCH_INDEX_VERSION: u32 = 2;
+
+/// Kayıtlardaki alan kodları: `(paket sırası << 2) | alan`
+const FIELD_NAME: u32 = 0;
+const FIELD_SUMMARY: u32 = 1;
+const FIELD_DESCRIPTION: u32 = 2;
+
+/// Ad içi aramada dizilenen en uzun harf dizisi; daha uzun terimler adaylar üzerinde doğrulanır
+const NAME_GRAM_LENGTH: usize = 3;
+
+/// Bir depo index dosyasının durumu; dosya yoksa sıfırdır
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SourceStamp {
+    repository: String,
+    modified: u64,
+    size: u64,
+}
+
+/// Kaynak index'ler ve depo sırası değişmediyse kayıtlı arama indeksi geçerlidir
+///
+/// Paketler öncelik sırasıyla tekilleştirildiği için bir deponun indeksi
+/// kendisinden önce gelen etkin depolara da bağlıdır.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct IndexStamp {
+    version: u32,
+    /// Bu depo ve ondan önce gelen etkin depolar, öncelik sırasıyla
+    sources: Vec<SourceStamp>,
+    package_count: usize,
+}
+
+/// Bulanık eşleşme için ad kelimesi; uzunluğa göre sıralı tutulur
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct NameTerm {
+    token: String,
+    /// Karakter sayısı
+    length: usize,
+    positions: Vec<u32>,
+}
+
+/// Tek bir deponun ters indeksi
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RepoIndex {
+    stamp: IndexStamp,
+    names: Vec<String>,
+    /// Katlanmış paket adları
+    folded_names: Vec<String>,
+    /// Terim -> kayıtlar; sıralı olduğu için önek araması yapılabilir
+    postings: BTreeMap<String, Vec<u32>>,
+    /// Yalnızca ad kelimeleri, uzunluğa göre sıralı
+    name_terms: Vec<NameTerm>,
+    /// Katlanmış adlardaki en fazla 3 harflik diziler -> paketler
+    name_grams: HashMap<String, Vec<u32>>,
+}
+
+impl RepoIndex {
+    fn build(packages: &[&PackageInfo], stamp: IndexStamp) -> Self {
+        let mut postings: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        let mut names = Vec::with_capacity(packages.len());
+        let mut folded_names = Vec::with_capacity(packages.len());
+
+        for (position, package) in packages.iter().enumerate() {
+            let position = position as u32;
+            names.push(package.name.clone());
+            folded_names.push(fold(&package.name));
+
+            let fields = [
+                (FIELD_NAME, &package.name),
+                (FIELD_SUMMARY, &package.summary),
+                (FIELD_DESCRIPTION, &package.description),
+            ];
+            for (field, text) in fields {
+                for token in PackageSearch::tokenize(text) {
+                    let entry = postings.entry(token).or_default();
+                    let posting = (position << 2) | field;
+                    // Aynı alanda tekrar eden kelimeyi bir kez kaydet; kayıtlar sıralı eklenir
+                    if entry.last() != Some(&posting) {
+                        entry.push(posting);
+                    }
+                }
+            }
+        }
+
+        let mut name_terms: Vec<NameTerm> = postings
+            .iter()
+            .filter_map(|(token, entries)| {
+                let positions: Vec<u32> = entries
+                    .iter()
+                    .filter(|&&posting| posting & 3 == FIELD_NAME)
+                    .map(|&posting| posting >> 2)
+                    .collect();
+                (!positions.is_empty()).then(|| NameTerm {
+                    token: token.clone(),
+                    length: token.chars().count(),
+                    positions,
+                })
+            })
+            .collect();
+        name_terms.sort_by_key(|term| term.length);
+
+        let mut name_grams: HashMap<String, Vec<u32>> = HashMap::new();
+        for (position, name) in folded_names.iter().enumerate() {
+            let chars: Vec<char> = name.chars().collect();
+            for length in 1..=NAME_GRAM_LENGTH {
+                for window in chars.windows(length) {
+                    let entry = name_grams.entry(window.iter().collect()).or_default();
+                    if entry.last() != Some(&(position as u32)) {
+                        entry.push(position as u32);
+                    }
+                }
+            }
+        }
+
+        Self { stamp, names, folded_names, postings, name_terms, name_grams }
+    }
+
+    /// Katlanmış adında `term` geçen paketler
+    fn names_containing(&self, term: &str) -> Vec<u32> {
+        let chars: Vec<char> = term.chars().collect();
+        if chars.is_empty() {
+            return Vec::new();
+        }
+
+        // En az paketi olan harf dizisinden adaylar alınır, sonra doğrulanır
+        let length = chars.len().min(NAME_GRAM_LENGTH);
+        let mut candidates: Option<&Vec<u32>> = None;
+        for window in chars.windows(length) {
+            let gram: String = window.iter().collect();
+            let Some(positions) = self.name_grams.get(&gram) else {
+                return Vec::new();
+            };
+            if candidates.is_none_or(|best| positions.len() < best.len()) {
+                candidates = Some(positions);
+            }
+        }
+
+        candidates
+            .map(|positions| {
+                positions
+                    .iter()
+                    .copied()
+                    .filter(|&position| chars.len() <= length || self.folded_names[position as usize].contains(term))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Terimin bu depodaki paketlere göre puanları
+    fn term_scores(&self, term: &str) -> HashMap<u32, u32> {
+        let mut scores: HashMap<u32, u32> = HashMap::new();
+        let mut add = |position: u32, score: u32| {
+            let best = scores.entry(position).or_insert(0);
+            *best = (*best).max(score);
+        };
+
+        // Tam kelime ve önek eşleşmeleri
+        for (token, entries) in self.postings.range(term.to_string()..) {
+            if !token.starts_with(term) {
+                break;
+            }
+            let exact = token == term;
+            for &posting in entries {
+                let position = posting >> 2;
+                let score = match posting & 3 {
+                    FIELD_NAME => {
+                        let name = &self.folded_names[position as usize];
+                        if name == term {
+                            SCORE_EXACT_NAME
+                        } else if name.starts_with(term) {
+                            SCORE_NAME_PREFIX
+                        } else if exact {
+                            SCORE_NAME_WORD
+                        } else {
+                            SCORE_NAME_CONTAINS
+                        }
+                    }
+                    FIELD_SUMMARY => SCORE_SUMMARY,
+                    _ => SCORE_DESCRIPTION,
+                };
+                add(position, score);
+            }
+        }
+
+        // Adın ortasında geçenler: "office" -> "libreoffice"
+        for position in self.names_containing(term) {
+            add(position, SCORE_NAME_CONTAINS);
+        }
+
+        // Yazım hatalarına karşı yalnızca ad kelimelerinde bulanık eşleşme
+        if let Some(allowed) = PackageSearch::allowed_typos(term) {
+            let length = term.chars().count();
+            let shortest = length.saturating_sub(allowed as usize);
+            let start = self.name_terms.partition_point(|name_term| name_term.length < shortest);
+            for name_term in &self.name_terms[start..] {
+                // Yazılmakta olan kelime için uzun kelimelerin aynı uzunluktaki başı denenir
+                let full = PackageSearch::edit_distance(term, &name_term.token, allowed);
+                let distance = if name_term.length > length {
+                    let end = name_term.token.char_indices().nth(length).map_or(name_term.token.len(), |(i, _)| i);
+                    full.into_iter()
+                        .chain(PackageSearch::edit_distance(term, &name_term.token[..end], allowed))
+                        .min()
+                } else {
+                    full
+                };
+                let Some(distance) = distance else {
+                    continue;
+                };
+                for &position in &name_term.positions {
+                    add(position, SCORE_FUZZY_NAME - distance * 100);
+                }
+            }
+        }
+
+        scores
+    }
+
+    fn search(&self, terms: &[String]) -> Vec<(u32, u32)> {
+        // Sorgunun tamamı bir paket adıysa: "firefox esr" -> "firefox-esr"
+        let joined = [terms.join("-"), terms.join("")];
+
+        let mut totals: Option<HashMap<u32, u32>> = None;
+        for term in terms {
+            let scores = self.term_scores(term);
+            totals = Some(match totals {
+                None => scores,
+                // Her terim eşleşmeli
+                Some(previous) => previous
+                    .into_iter()
+                    .filter_map(|(position, total)| Some((position, total + scores.get(&position)?)))
+                    .collect(),
+            });
+        }
+
+        let mut results: Vec<(u32, u32)> = totals.unwrap_or_default().into_iter().collect();
+        for (position, score) in &mut results {
+            if joined.contains(&self.folded_names[*position as usize]) {
+                *score = (*score).max(SCORE_EXACT_NAME * terms.len() as u32);
+            }
+        }
+        results
+    }
+}
+
+/// Depo başına ters indeks, `index_cache_dir()/<repo>/search-index.json` olarak saklanır
+#[derive(Debug, Clone, Default)]
+pub struct SearchIndex {
+    repos: Vec<(String, RepoIndex)>,
+}
+
+impl SearchIndex {
+    /// Kayıtlı indeksleri yükle; kaynak index'i ya da depo sırası değişen depoları yeniden oluştur
+    ///
+    /// `repositories` paketlerin yüklendiği etkin depolardır, öncelik sırasıyla.
+    pub fn load_or_build(packages: &[PackageInfo], repositories: &[&str]) -> Self {
+        Self::load_or_build_in(&paths::index_cache_dir(), &XmlParser::index_path, packages, repositories)
+    }
+
+    /// `cache_dir` altında saklanan indeksler; `source_path` depo index dosyasını verir
+    fn load_or_build_in(
+        cache_dir: &Path,
+        source_path: &dyn Fn(&str) -> PathBuf,
+        packages: &[PackageInfo],
+        repositories: &[&str],
+    ) -> Self {
+        let mut by_repo: Vec<(&str, Vec<&PackageInfo>)> = Vec::new();
+        for package in packages {
+            match by_repo.iter_mut().find(|(repo, _)| *repo == &*package.repository) {
+                Some((_, list)) => list.push(package),
+                None => by_repo.push((&package.repository, vec![package])),
+            }
+        }
+
+        let repos = by_repo
+            .into_iter()
+            .map(|(repo, packages)| {
+                let stamp = Self::stamp(source_path, repo, repositories, packages.len());
+                let index = match stamp.as_ref().and_then(|stamp| Self::load(cache_dir, repo, stamp)) {
+                    Some(index) => index,
+                    None => {
+                        println!("Building search index for {} ({} packages)", repo, packages.len());
+                        let index = RepoIndex::build(&packages, stamp.clone().unwrap_or_default());
+                        // Kaynak dosyası olmayan (örnek) veriler diske yazılmaz
+                        if stamp.is_some() {
+                            if let Err(e) = Self::save(cache_dir, repo, &index) {
+                                println!("Could not save search index for {}: {}", repo, e);
+                            }
+                        }
+                        index
+                    }
+                };
+                (repo.to_string(), index)
+            })
+            .collect();
+
+        Self { repos }
+    }
+
+    /// Sonuçlar en iyi eşleşme önce; eşit puanda kısa ad önce gelir
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms = PackageSearch::tokenize(query);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut hits: Vec<SearchHit> = self
+            .repos
+            .iter()
+            .flat_map(|(_, index)| {
+                index.search(&terms).into_iter().map(|(position, score)| SearchHit {
+                    name: index.names[position as usize].clone(),
+                    score,
+                })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| a.name.len().cmp(&b.name.len()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        hits
+    }
+
+    fn file_path(cache_dir: &Path, repo: &str) -> PathBuf {
+        cache_dir.join(repo).join("search-index.json")
+    }
+
+    /// Depo listede yoksa ya da index dosyası okunamıyorsa (örnek veri) None
+    fn stamp(
+        source_path: &dyn Fn(&str) -> PathBuf,
+        repo: &str,
+        repositories: &[&str],
+        package_count: usize,
+    ) -> Option<IndexStamp> {
+        let position = repositories.iter().position(|name| *name == repo)?;
+        let sources = repositories[..=position]
+            .iter()
+            .map(|name| {
+                let metadata = std::fs::metadata(source_path(name)).ok();
+                let modified = metadata
+                    .as_ref()
+                    .and_then(|m| m.modified().ok())
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map_or(0, |duration| duration.as_secs());
+                SourceStamp {
+                    repository: name.to_string(),
+                    modified,
+                    size: metadata.map_or(0, |m| m.len()),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Kendi index dosyası olmayan deponun indeksi kaydedilmez
+        if sources.last().is_none_or(|source| source.modified == 0) {
+            return None;
+        }
+        Some(IndexStamp {
+            version: SEARCH_INDEX_VERSION,
+            sources,
+            package_count,
+        })
+    }
+
+    fn load(cache_dir: &Path, repo: &str, stamp: &IndexStamp) -> Option<RepoIndex> {
+        let content = std::fs::read(Self::file_path(cache_dir, repo)).ok()?;
+        let index: RepoIndex = serde_json::from_slice(&content).ok()?;
+        (index.stamp == *stamp).then_some(index)
+    }
+
+    fn save(cache_dir: &Path, repo: &str, index: &RepoIndex) -> Result<()> {
+        let path = Self::file_path(cache_dir, repo);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_vec(index)?)?;
+        std::fs::rename(&temp_path, &path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo_index(names: &[&str]) -> RepoIndex {
+        let packages: Vec<PackageInfo> = names.iter().map(|name| PackageInfo::for_test(name, "Media player", "")).collect();
+        RepoIndex::build(&packages.iter().collect::<Vec<_>>(), IndexStamp::default())
+    }
+
+    fn temp_dir(test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("pisi-search-index-test-{}-{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn finds_names_through_grams() {
+        let index = repo_index(&["libreoffice", "office", "firefox", "vlc"]);
+
+        assert_eq!(index.names_containing("off"), [0, 1]);
+        assert_eq!(index.names_containing("o"), [0, 1, 2]);
+        // Üç harften uzun terimler adaylar üzerinde doğrulanır
+        assert_eq!(index.names_containing("reof"), [0]);
+        assert_eq!(index.names_containing("ffice"), [0, 1]);
+        assert_eq!(index.names_containing("ofic"), Vec::<u32>::new());
+        assert_eq!(index.names_containing("xyz"), Vec::<u32>::new());
+        assert_eq!(index.names_containing(""), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn fuzzy_matches_only_name_vocabulary() {
+        let index = repo_index(&["firefox", "thunderbird", "vlc"]);
+
+        assert_eq!(index.term_scores("firefx").get(&0), Some(&(SCORE_FUZZY_NAME - 100)));
+        assert_eq!(index.term_scores("thunderbrid").get(&1), Some(&(SCORE_FUZZY_NAME - 100)));
+        // Yazılmakta olan kelime uzun adın başıyla karşılaştırılır
+        assert_eq!(index.term_scores("thundr").get(&1), Some(&(SCORE_FUZZY_NAME - 100)));
+        // Kısa terimlerde ve özet kelimelerinde bulanık eşleşme yok
+        assert!(index.term_scores("vlx").is_empty());
+        assert!(index.term_scores("plaier").is_empty());
+        assert_eq!(index.term_scores("player").get(&2), Some(&SCORE_SUMMARY));
+    }
+
+    #[test]
+    fn stamp_depends_on_repo_order_and_source_files() {
+        let dir = temp_dir("stamp");
+        std::fs::write(dir.join("main"), "<PISI/>").unwrap();
+        std::fs::write(dir.join("contrib"), "<PISI/>").unwrap();
+        let source_path = |repo: &str| dir.join(repo);
+        let stamp = |repositories: &[&str]| SearchIndex::stamp(&source_path, "contrib", repositories, 10);
+
+        let current = stamp(&["main", "contrib"]).unwrap();
+        assert_eq!(stamp(&["main", "contrib"]), Some(current.clone()));
+        assert_ne!(stamp(&["contrib", "main"]), Some(current.clone()));
+        assert_ne!(stamp(&["contrib"]), Some(current.clone()));
+        assert_eq!(stamp(&["main"]), None);
+
+        // Önce gelen deponun index'i değişirse bu deponun indeksi de eskir
+        std::fs::write(dir.join("main"), "<PISI></PISI>").unwrap();
+        assert_ne!(stamp(&["main", "contrib"]), Some(current));
+
+        std::fs::remove_file(dir.join("contrib")).unwrap();
+        assert_eq!(stamp(&["main", "contrib"]), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn rebuilds_index_saved_with_stale_stamp() {
+        let dir = temp_dir("round-trip");
+        let cache_dir = dir.join("cache");
+        std::fs::write(dir.join("main"), "<PISI/>").unwrap();
+        let source_path = |repo: &str| dir.join(repo);
+        let packages = [PackageInfo::for_test("firefox", "Web browser", ""), PackageInfo::for_test("vlc", "Media player", "")];
+        let load = || SearchIndex::load_or_build_in(&cache_dir, &source_path, &packages, &["main"]);
+        let saved = || -> RepoIndex {
+            serde_json::from_slice(&std::fs::read(SearchIndex::file_path(&cache_dir, "main")).unwrap()).unwrap()
+        };
+
+        load();
+        let stamp = SearchIndex::stamp(&source_path, "main", &["main"], packages.len()).unwrap();
+        assert_eq!(saved().stamp, stamp);
+
+        // Damga geçerliyse kayıtlı dosya kullanılır
+        let mut cached = saved();
+        cached.names = vec!["cached".to_string(), "cached".to_string()];
+        SearchIndex::save(&cache_dir, "main", &cached).unwrap();
+        assert_eq!(load().repos[0].1.names, ["cached", "cached"]);
+
+        // Eski damgalı dosya yeniden oluşturulup üzerine yazılır
+        cached.stamp.sources[0].modified -= 1;
+        SearchIndex::save(&cache_dir, "main", &cached).unwrap();
+        assert_eq!(load().repos[0].1.names, ["firefox", "vlc"]);
+        assert_eq!(saved().stamp, stamp);
+        assert_eq!(saved().names, ["firefox", "vlc"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,6 +1,10 @@
 use anyhow::Result;
 use roxmltree::Document;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use crate::config::paths;
 
 #[derive(Debug, Clone)]
 pub struct PackageInfo {
@@ -9,17 +13,55 @@ pub struct PackageInfo {
     pub description: String,
     pub version: String,
     pub release: u32,
-    pub license: String,
-    pub part_of: String,
+    pub license: Arc<str>,
+    pub part_of: Arc<str>,
     pub package_size: u64,
     pub installed_size: u64,
     pub package_format: String,
     pub distribution: String,
     pub distribution_release: String,
-    pub architecture: String,
+    pub architecture: Arc<str>,
+    /// Paketin geldiği depo
+    pub repository: Arc<str>,
     pub source: Option<Source>,
     pub history: Vec<PackageHistory>,
     pub dependencies: Vec<Dependency>,
+    /// <Provides> altındaki pkg-config adları vb.
+    pub provides: Vec<Provide>,
+}
+
+impl PackageInfo {
+    /// History'deki en yeni tarih ("YYYY-MM-DD"), yoksa boş
+    pub fn last_updated(&self) -> &str {
+        self.history.iter().map(|entry| entry.date.as_str()).max().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+impl PackageInfo {
+    /// Testler için "main" deposunda, yalnızca metinleri dolu paket
+    pub(crate) fn for_test(name: &str, summary: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            summary: summary.to_string(),
+            description: description.to_string(),
+            version: "1.0".to_string(),
+            release: 1,
+            license: "GPLv2".into(),
+            part_of: "desktop".into(),
+            package_size: 0,
+            installed_size: 0,
+            package_format: String::new(),
+            distribution: String::new(),
+            distribution_release: String::new(),
+            architecture: "x86_64".into(),
+            repository: "main".into(),
+            source: None,
+            history: Vec::new(),
+            dependencies: Vec::new(),
+            provides: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -28,11 +70,29 @@ pub struct Source {
     pub homepage: String,
 }
 
+/// History'deki bir <Update> kaydı
 #[derive(Debug, Clone)]
 pub struct PackageHistory {
     pub version: String,
     pub release: u32,
     pub date: String,
+    /// `type` özniteliği ("security", "critical"); çoğu kayıtta yoktur
+    pub update_type: Option<String>,
+    pub comment: String,
+    /// Paketçinin adı ve e-postası
+    pub name: String,
+    pub email: String,
+}
+
+impl PackageHistory {
+    /// Kurulu sürümden ("115.0-12") sonra mı gelmiş; pisi release'i her
+    /// güncellemede artırdığı için yalnızca release karşılaştırılır
+    pub fn is_newer_than(&self, installed_version: &str) -> bool {
+        installed_version
+            .rsplit_once('-')
+            .and_then(|(_, release)| release.parse::<u32>().ok())
+            .is_some_and(|installed_release| self.release > installed_release)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -42,100 +102,354 @@ pub struct Dependency {
     pub release: Option<u32>,
 }
 
+/// <Provides> kaydı: tür etiket adıdır ("PkgConfig", "PkgConfig32" ...)
+#[derive(Debug, Clone)]
+pub struct Provide {
+    pub kind: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PackageFile {
+    pub path: String,
+    pub file_type: String,
+    pub size: u64,
+}
+
+/// Bir depo index'inin özet bilgisi
+#[derive(Debug, Clone)]
+pub struct IndexSummary {
+    pub distribution: String,
+    pub release: String,
+    pub package_count: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct Component {
     pub name: String,
     pub package_count: usize,
 }
 
+/// Bir iş parçacığına verilecek en az paket sayısı; küçük index'ler tek parça kalır
+const MIN_PACKAGES_PER_CHUNK: usize = 256;
+
+/// Index yüklemesinin ilerlemesi; yükleyici iş parçacıkları yazar, arayüz okur
+#[derive(Debug, Default)]
+pub struct LoadProgress {
+    repositories_total: AtomicUsize,
+    repositories_done: AtomicUsize,
+    /// XML'i okunup paket sayısı `packages_total`'a eklenen depolar
+    repositories_counted: AtomicUsize,
+    packages_total: AtomicUsize,
+    packages_parsed: AtomicUsize,
+}
+
+impl LoadProgress {
+    /// (biten, toplam) depo sayısı
+    pub fn repositories(&self) -> (usize, usize) {
+        (
+            self.repositories_done.load(Ordering::Relaxed),
+            self.repositories_total.load(Ordering::Relaxed),
+        )
+    }
+
+    /// (çevrilen, bilinen) paket sayısı; toplam, her depo okundukça artar
+    pub fn packages(&self) -> (usize, usize) {
+        (
+            self.packages_parsed.load(Ordering::Relaxed),
+            self.packages_total.load(Ordering::Relaxed),
+        )
+    }
+
+    /// İlerleme çubuğu için 0.0 - 1.0; paket sayısı henüz bilinmeyen depolar
+    /// tamamlanmamış sayılır
+    pub fn fraction(&self) -> f32 {
+        let total = self.repositories_total.load(Ordering::Relaxed);
+        let counted = self.repositories_counted.load(Ordering::Relaxed);
+        let (parsed, known) = self.packages();
+        if total == 0 || known == 0 {
+            return 0.0;
+        }
+        (parsed as f32 / known as f32) * (counted as f32 / total as f32)
+    }
+}
+
 pub struct XmlParser;
 
 impl XmlParser {
-    pub fn load_pisi_index() -> Result<Vec<PackageInfo>> {
-        let path = "/var/lib/pisi/index/Stable/pisi-index.xml";
-        println!("Loading Pisi index from: {}", path);
+    pub fn load_pisi_index(repository: &str, progress: &LoadProgress) -> Result<Vec<PackageInfo>> {
+        let path = Self::index_path(repository);
+        println!("Loading Pisi index from: {}", path.display());
         
-        let xml_content = std::fs::read_to_string(path)
+        let xml_content = std::fs::read_to_string(&path)
             .map_err(|e| anyhow::anyhow!("Failed to read Pisi index file: {}", e))?;
         
-        Self::parse_pisi_index(&xml_content)
+        let mut packages = Self::parse_pisi_index_with_progress(&xml_content, progress)?;
+        let repository: Arc<str> = Arc::from(repository);
+        for package in &mut packages {
+            package.repository = repository.clone();
+        }
+        Ok(packages)
     }
 
-    pub fn parse_pisi_index(xml_content: &str) -> Result<Vec<PackageInfo>> {
-        let doc = Document::parse(xml_content)?;
-        let mut packages = Vec::new();
+    /// pisi'nin index'i ile uygulamanın indirdiği index'ten daha yeni olanı
+    pub fn index_path(repository: &str) -> PathBuf {
+        let system = PathBuf::from(format!("/var/lib/pisi/index/{}/pisi-index.xml", repository));
+        let cached = paths::index_cache_dir().join(repository).join("pisi-index.xml");
 
-        // Tüm <Package> tag'lerini bul, ancak <Obsoletes> içindekileri atla
-        for node in doc.descendants().filter(|n| n.has_tag_name("Package")) {
-            // Eğer bu paket <Obsoletes> içindeyse atla
-            if Self::is_in_obsoletes(&node) {
-                continue;
-            }
+        let modified = |path: &PathBuf| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        match (modified(&system), modified(&cached)) {
+            (Some(system_time), Some(cached_time)) if cached_time > system_time => cached,
+            (None, Some(_)) => cached,
+            _ => system,
+        }
+    }
 
-            let package_name = Self::get_text(&node, "Name")
-                .unwrap_or_else(|| "Unknown".to_string());
-            
-            // Eğer paket ismi hala "Unknown" ise, debug et
-            if package_name == "Unknown" && packages.len() < 3 {
-                println!("=== DEBUG: Package with Unknown name ===");
-                println!("Node: {:?}", node.tag_name().name());
-                println!("All direct children:");
-                for child in node.children() {
-                    if child.is_element() {
-                        println!("  - {}: {:?}", child.tag_name().name(), child.text());
+    /// Depoları ayrı iş parçacıklarında yükle, öncelik sırasıyla birleştir;
+    /// aynı paket birden fazla depoda varsa listede önce gelen depo kazanır
+    pub fn load_repository_indexes(repositories: &[&str], progress: &LoadProgress) -> Result<Vec<PackageInfo>> {
+        progress.repositories_total.store(repositories.len(), Ordering::Relaxed);
+
+        let results: Vec<Result<Vec<PackageInfo>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = repositories
+                .iter()
+                .map(|repository| {
+                    scope.spawn(move || {
+                        let result = Self::load_pisi_index(repository, progress);
+                        progress.repositories_done.fetch_add(1, Ordering::Relaxed);
+                        result
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|_| Err(anyhow::anyhow!("Index loader thread panicked"))))
+                .collect()
+        });
+
+        let mut packages: Vec<PackageInfo> = Vec::new();
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut loaded_any = false;
+
+        // Sonuçlar iş parçacıklarının bitiş sırasına değil depo sırasına göre birleşir
+        for (repository, result) in repositories.iter().zip(results) {
+            match result {
+                Ok(repo_packages) => {
+                    loaded_any = true;
+                    for package in repo_packages {
+                        if seen.insert(package.name.clone()) {
+                            packages.push(package);
+                        }
                     }
                 }
-                println!("================");
+                Err(e) => println!("Skipping repository {}: {}", repository, e),
             }
+        }
 
-            let part_of = Self::get_text(&node, "PartOf").unwrap_or_else(|| "system".to_string());
-            let version = Self::get_text(&node, "Version").unwrap_or_default();
-            
-            let package_size = Self::get_text(&node, "PackageSize")
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(0);
-                
-            let installed_size = Self::get_text(&node, "InstalledSize")
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(0);
-
-            let package = PackageInfo {
-                name: package_name.clone(),
-                summary: Self::get_text(&node, "Summary").unwrap_or_default(),
-                description: Self::get_text(&node, "Description").unwrap_or_default(),
-                version: version.clone(),
-                release: 1,
-                license: Self::get_text(&node, "License").unwrap_or_default(),
-                part_of: part_of.clone(),
-                package_size,
-                installed_size,
-                package_format: Self::get_text(&node, "PackageFormat").unwrap_or_else(|| "1.2".to_string()),
-                distribution: Self::get_text(&node, "Distribution").unwrap_or_else(|| "PisiLinux".to_string()),
-                distribution_release: Self::get_text(&node, "DistributionRelease").unwrap_or_else(|| "2.0".to_string()),
-                architecture: Self::get_text(&node, "Architecture").unwrap_or_else(|| "x86_64".to_string()),
-                source: Self::parse_source(&node),
-                history: Self::parse_history(&node),
-                dependencies: Self::parse_dependencies(&node),
-            };
-            
-            // Sadece geçerli paketleri ekle (isim ve versiyonu olan)
-            if package_name != "Unknown" && !version.is_empty() {
-                packages.push(package);
-            }
+        if !loaded_any {
+            return Err(anyhow::anyhow!("No repository index could be loaded"));
         }
+        Ok(packages)
+    }
+
+    /// İndirilen index verisini metne çevir, .xz ise önce aç
+    pub fn decode_index(bytes: &[u8], url: &str) -> Result<String> {
+        if url.ends_with(".xz") {
+            let mut decompressed = Vec::new();
+            lzma_rs::xz_decompress(&mut &bytes[..], &mut decompressed)
+                .map_err(|e| anyhow::anyhow!("Failed to decompress {}: {:?}", url, e))?;
+            Ok(String::from_utf8(decompressed)?)
+        } else if url.ends_with(".xml") {
+            Ok(String::from_utf8(bytes.to_vec())?)
+        } else {
+            Err(anyhow::anyhow!("Unsupported index format, expected pisi-index.xml or pisi-index.xml.xz"))
+        }
+    }
+
+    /// Index'teki dağıtım bilgisi ve paket sayısı
+    pub fn parse_index_summary(xml_content: &str) -> Result<IndexSummary> {
+        let doc = Document::parse(xml_content)?;
+        let root = doc.root_element();
 
-        // Debug: İlk 3 paketi göster
-        if !packages.is_empty() {
-            println!("First 3 packages parsed correctly:");
-            for pkg in packages.iter().take(3) {
-                println!("  - {}: {} (PartOf: {})", pkg.name, pkg.summary, pkg.part_of);
+        let distribution_node = root.children().find(|n| n.has_tag_name("Distribution"));
+        let distribution = distribution_node
+            .and_then(|n| Self::get_text(&n, "SourceName"))
+            .unwrap_or_default();
+        let release = distribution_node
+            .and_then(|n| Self::get_text(&n, "Version"))
+            .unwrap_or_default();
+
+        let package_count = root
+            .children()
+            .filter(|n| n.has_tag_name("Package"))
+            .count();
+
+        if distribution.is_empty() && package_count == 0 {
+            return Err(anyhow::anyhow!("Document does not look like a Pisi index"));
+        }
+
+        Ok(IndexSummary {
+            distribution,
+            release,
+            package_count,
+        })
+    }
+
+    pub fn parse_pisi_index(xml_content: &str) -> Result<Vec<PackageInfo>> {
+        Self::parse_pisi_index_with_progress(xml_content, &LoadProgress::default())
+    }
+
+    /// <Package> düğümlerini parçalara bölüp paralel çevir; parçalar index
+    /// sırasıyla birleştiği için sonuç seri ayrıştırmayla aynıdır
+    pub fn parse_pisi_index_with_progress(xml_content: &str, progress: &LoadProgress) -> Result<Vec<PackageInfo>> {
+        let doc = Document::parse(xml_content)?;
+
+        // Tüm <Package> tag'lerini bul, ancak <Obsoletes> içindekileri atla
+        let nodes: Vec<roxmltree::Node> = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("Package") && !Self::is_in_obsoletes(n))
+            .collect();
+        progress.packages_total.fetch_add(nodes.len(), Ordering::Relaxed);
+        progress.repositories_counted.fetch_add(1, Ordering::Relaxed);
+
+        let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        let chunk_size = nodes.len().div_ceil(threads).max(MIN_PACKAGES_PER_CHUNK);
+
+        let chunks: Vec<Result<Vec<PackageInfo>>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = nodes
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|node| {
+                                let package = Self::parse_package_node(node);
+                                progress.packages_parsed.fetch_add(1, Ordering::Relaxed);
+                                package
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().map_err(|_| anyhow::anyhow!("Index parser thread panicked")))
+                .collect()
+        });
+
+        let mut packages = Vec::with_capacity(nodes.len());
+        let mut skipped = 0;
+        for chunk in chunks {
+            for package in chunk? {
+                // Sadece geçerli paketleri ekle (isim ve versiyonu olan)
+                if package.name != "Unknown" && !package.version.is_empty() {
+                    packages.push(package);
+                } else {
+                    skipped += 1;
+                }
             }
         }
 
+        if skipped > 0 {
+            println!("Skipped {} packages without a name or version", skipped);
+        }
         println!("Successfully parsed {} valid packages from Pisi index", packages.len());
         Ok(packages)
     }
 
+    /// Tek bir <Package> düğümünü PackageInfo'ya çevir
+    fn parse_package_node(node: &roxmltree::Node) -> PackageInfo {
+        let part_of = Self::get_text(node, "PartOf").unwrap_or_else(|| "system".to_string());
+        let package_size = Self::get_text(node, "PackageSize")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+            
+        let installed_size = Self::get_text(node, "InstalledSize")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+
+        // Paketin sürümü ve release'i en yeni <Update release="..."> kaydıdır
+        let history = Self::parse_history(node);
+        let newest = history.iter().max_by_key(|entry| entry.release);
+        let release = newest.map_or(1, |entry| entry.release);
+        let version = newest
+            .map(|entry| entry.version.clone())
+            .filter(|version| !version.is_empty())
+            .or_else(|| Self::get_text(node, "Version"))
+            .unwrap_or_default();
+
+        PackageInfo {
+            name: Self::get_text(node, "Name").unwrap_or_else(|| "Unknown".to_string()),
+            summary: Self::get_text(node, "Summary").unwrap_or_default(),
+            description: Self::get_text(node, "Description").unwrap_or_default(),
+            version,
+            release,
+            license: Self::get_text(node, "License").unwrap_or_default().into(),
+            part_of: part_of.into(),
+            package_size,
+            installed_size,
+            package_format: Self::get_text(node, "PackageFormat").unwrap_or_else(|| "1.2".to_string()),
+            distribution: Self::get_text(node, "Distribution").unwrap_or_else(|| "PisiLinux".to_string()),
+            distribution_release: Self::get_text(node, "DistributionRelease").unwrap_or_else(|| "2.0".to_string()),
+            architecture: Self::get_text(node, "Architecture").unwrap_or_else(|| "x86_64".to_string()).into(),
+            repository: Arc::from(""),
+            source: Self::parse_source(node),
+            history,
+            dependencies: Self::parse_dependencies(node),
+            provides: Self::parse_provides(node),
+        }
+    }
+
+    /// .pisi arşivindeki metadata.xml içeriğini parse et
+    pub fn parse_package_metadata(xml_content: &str) -> Result<(PackageInfo, Vec<String>)> {
+        let doc = Document::parse(xml_content)?;
+        let root = doc.root_element();
+
+        let package_node = root
+            .children()
+            .find(|n| n.has_tag_name("Package"))
+            .ok_or_else(|| anyhow::anyhow!("metadata.xml has no <Package> element"))?;
+
+        let mut package = Self::parse_package_node(&package_node);
+        // metadata.xml'de <Source>, <Package> ile aynı seviyede durur
+        if package.source.is_none() {
+            package.source = Self::parse_source(&root);
+        }
+
+        let replaces = package_node
+            .children()
+            .filter(|n| n.has_tag_name("Replaces"))
+            .flat_map(|n| n.children().filter(|c| c.has_tag_name("Package")))
+            .filter_map(|n| n.text())
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        Ok((package, replaces))
+    }
+
+    /// files.xml içeriğini parse et
+    pub fn parse_files_xml(xml_content: &str) -> Result<Vec<PackageFile>> {
+        let doc = Document::parse(xml_content)?;
+
+        let files = doc
+            .descendants()
+            .filter(|n| n.has_tag_name("File"))
+            .filter_map(|node| {
+                let path = Self::get_text(&node, "Path")?;
+                Some(PackageFile {
+                    path,
+                    file_type: Self::get_text(&node, "Type").unwrap_or_default(),
+                    size: Self::get_text(&node, "Size")
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(0),
+                })
+            })
+            .collect();
+
+        Ok(files)
+    }
+
     /// <Obsoletes> tag'i içindeki paketleri kontrol et
     fn is_in_obsoletes(node: &roxmltree::Node) -> bool {
         let mut current = node.parent();
@@ -246,8 +560,13 @@ impl XmlParser {
 
     fn parse_source(node: &roxmltree::Node) -> Option<Source> {
         if let Some(source_node) = node.descendants().find(|n| n.has_tag_name("Source")) {
-            let name = source_node.attribute("name").unwrap_or("").to_string();
-            let homepage = source_node.attribute("homepage").unwrap_or("").to_string();
+            // Index'te attribute, metadata.xml'de child element olarak gelebilir
+            let name = source_node.attribute("name").map(|s| s.to_string())
+                .or_else(|| Self::get_text(&source_node, "Name"))
+                .unwrap_or_default();
+            let homepage = source_node.attribute("homepage").map(|s| s.to_string())
+                .or_else(|| Self::get_text(&source_node, "Homepage"))
+                .unwrap_or_default();
             
             if !name.is_empty() {
                 return Some(Source { name, homepage });
@@ -261,13 +580,26 @@ impl XmlParser {
         
         if let Some(history_node) = node.descendants().find(|n| n.has_tag_name("History")) {
             for update_node in history_node.descendants().filter(|n| n.has_tag_name("Update")) {
-                let version = update_node.attribute("version").unwrap_or("").to_string();
+                // pisi-index.xml'de sürüm ve tarih alt elemanlardır: <Update release="3"><Date>...
+                let version = update_node.attribute("version")
+                    .map(str::to_string)
+                    .or_else(|| Self::get_text(&update_node, "Version"))
+                    .unwrap_or_default();
                 let release = update_node.attribute("release")
                     .and_then(|s| s.parse().ok())
                     .unwrap_or(1);
-                let date = update_node.attribute("date").unwrap_or("").to_string();
+                let date = update_node.attribute("date")
+                    .map(str::to_string)
+                    .or_else(|| Self::get_text(&update_node, "Date"))
+                    .unwrap_or_default();
+                let update_type = update_node.attribute("type")
+                    .map(str::to_string)
+                    .filter(|kind| !kind.is_empty());
+                let comment = Self::get_text(&update_node, "Comment").unwrap_or_default();
+                let name = Self::get_text(&update_node, "Name").unwrap_or_default();
+                let email = Self::get_text(&update_node, "Email").unwrap_or_default();
                 
-                history.push(PackageHistory { version, release, date });
+                history.push(PackageHistory { version, release, date, update_type, comment, name, email });
             }
         }
         
@@ -291,4 +623,20 @@ impl XmlParser {
         
         deps
     }
+
+    /// COMAR betikleri bir şey sağlamaz, atlanır
+    fn parse_provides(node: &roxmltree::Node) -> Vec<Provide> {
+        node.children()
+            .filter(|n| n.has_tag_name("Provides"))
+            .flat_map(|n| n.children().filter(|c| c.is_element()))
+            .filter(|n| !n.has_tag_name("COMAR"))
+            .filter_map(|n| {
+                let name = n.text()?.trim();
+                (!name.is_empty()).then(|| Provide {
+                    kind: n.tag_name().name().to_string(),
+                    name: name.to_string(),
+                })
+            })
+            .collect()
+    }
 }
@@ -1,5 +1,7 @@
 pub mod settings;
 pub mod themes;
+pub mod paths;
+pub mod system_theme;
 
 pub use settings::*;
 pub use themes::*;
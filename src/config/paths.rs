@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+const APP_DIR: &str = "pisi-package-manager";
+
+/// $XDG_CONFIG_HOME/pisi-package-manager, yoksa ~/.config/pisi-package-manager
+pub fn config_dir() -> PathBuf {
+    xdg_config_home().join(APP_DIR)
+}
+
+/// Kullanıcı tema dosyaları (*.json)
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
+}
+
+/// $XDG_CONFIG_HOME, yoksa ~/.config (GTK/KDE ayar dosyaları burada)
+pub fn xdg_config_home() -> PathBuf {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+pub fn settings_file() -> PathBuf {
+    config_dir().join("settings.json")
+}
+
+/// $XDG_CACHE_HOME/pisi-package-manager, yoksa ~/.cache/pisi-package-manager
+pub fn cache_dir() -> PathBuf {
+    xdg_dir("XDG_CACHE_HOME", ".cache").join(APP_DIR)
+}
+
+/// Uygulamanın indirdiği depo index'leri
+pub fn index_cache_dir() -> PathBuf {
+    cache_dir().join("index")
+}
+
+fn xdg_dir(variable: &str, home_fallback: &str) -> PathBuf {
+    std::env::var_os(variable)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .unwrap_or_else(|| {
+            let home = std::env::var_os("HOME").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("/tmp"));
+            home.join(home_fallback)
+        })
+}
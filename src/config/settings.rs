@@ -1,20 +1,25 @@
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::Path;
+use crate::backend::collections::SmartCollection;
+use crate::config::{paths, Theme};
+
+/// Ayar dosyası biçimi değiştikçe artırılır, eski dosyalar `migrate` ile taşınır
+pub const SETTINGS_SCHEMA_VERSION: u32 = 6;
 
 // SettingsTab'ı basitleştiriyoruz
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum SettingsTab {
+    #[default]
     General,
     Cache,
     Repos,
     Proxy,
 }
 
-impl Default for SettingsTab {
-    fn default() -> Self {
-        Self::General
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageListSettings {
     pub show_only_desktop_apps: bool,
@@ -34,6 +39,20 @@ pub struct CacheSettings {
     pub use_disk_cache: bool,
     pub cache_size_mb: u32,
     pub cache_directory: String,
+    #[serde(default)]
+    pub use_bandwidth_limit: bool,
+    #[serde(default)]
+    pub bandwidth_limit_kbps: u32,
+    /// İşlemlerden sonra önbelleği boyut sınırına indir; kullanıcı açmadıkça kapalı
+    #[serde(default)]
+    pub trim_after_operations: bool,
+}
+
+impl CacheSettings {
+    /// Etkin bant genişliği sınırı (KB/s), sınır yoksa None
+    pub fn bandwidth_limit(&self) -> Option<u32> {
+        Some(self.bandwidth_limit_kbps).filter(|&limit| self.use_bandwidth_limit && limit > 0)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,7 +67,7 @@ pub struct ProxySettings {
     pub use_http_proxy_for_all: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Repository {
     pub enabled: bool,
     pub name: String,
@@ -57,17 +76,192 @@ pub struct Repository {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
+    /// Sürüm alanı olmayan dosyalar 0 kabul edilir
+    #[serde(default)]
+    pub schema_version: u32,
     pub package_list: PackageListSettings,
     pub update: UpdateSettings,
     pub cache: CacheSettings,
     pub proxy: ProxySettings,
     pub repositories: Vec<Repository>,
     pub system_tray_enabled: bool,
+    /// Güncellemelerden hariç tutulan (sabitlenmiş) paketler
+    #[serde(default)]
+    pub held_packages: Vec<String>,
+    /// Light, Dark ya da System (masaüstünü izle)
+    #[serde(default)]
+    pub theme: Theme,
+    /// Arayüz dili ("tr", "en"); boşsa ortamdaki dil kullanılır
+    #[serde(default)]
+    pub language: String,
+    /// Kenar çubuğundaki kayıtlı aramalar
+    #[serde(default)]
+    pub smart_collections: Vec<SmartCollection>,
+}
+
+impl AppSettings {
+    /// Ayarları XDG config dizininden yükle
+    ///
+    /// Dosya yoksa None döner. Bozuk dosya kenara alınır ve yine None ile bir
+    /// uyarı döner; böylece ilk çalıştırmadaki pisi.conf aktarımı atlanmaz.
+    pub fn load() -> (Option<Self>, Option<String>) {
+        Self::load_from(&paths::settings_file())
+    }
+
+    pub fn load_from(path: &Path) -> (Option<Self>, Option<String>) {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return (None, None),
+            Err(e) => {
+                return (None, Some(format!("Could not read {}: {}", path.display(), e)));
+            }
+        };
+
+        let parsed = serde_json::from_str::<Value>(&content)
+            .map_err(anyhow::Error::from)
+            .and_then(|value| Ok(serde_json::from_value::<Self>(Self::migrate(value))?));
+
+        match parsed {
+            Ok(settings) => {
+                let warning = Self::newer_schema(&content).map(|version| {
+                    format!(
+                        "Settings file is from a newer version (schema {}), changes will not be saved",
+                        version
+                    )
+                });
+                (Some(settings), warning)
+            }
+            Err(e) => {
+                // Yeni sürümün dosyası bozuk değildir, yerinde kalmalı
+                if let Some(version) = Self::newer_schema(&content) {
+                    let warning = format!(
+                        "Settings file is from a newer version (schema {}) and could not be read ({}), changes will not be saved",
+                        version, e
+                    );
+                    println!("{}", warning);
+                    return (Some(Self::default()), Some(warning));
+                }
+
+                // Bozuk dosyayı silme, kullanıcı inceleyebilsin
+                let backup = path.with_extension("json.corrupt");
+                let _ = std::fs::rename(path, &backup);
+                let warning = format!(
+                    "Settings file was corrupt ({}), defaults restored. Old file kept as {}",
+                    e,
+                    backup.display()
+                );
+                println!("{}", warning);
+                (None, Some(warning))
+            }
+        }
+    }
+
+    /// Eski sürüm ayar dosyalarını güncel şemaya taşı
+    fn migrate(mut value: Value) -> Value {
+        let version = value
+            .get("schema_version")
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as u32;
+
+        if version > SETTINGS_SCHEMA_VERSION {
+            println!(
+                "Settings file is from a newer version ({}), unknown fields will be ignored",
+                version
+            );
+        }
+
+        // Şimdiye kadarki sürümler yalnızca alan ekledi; taşımak eksik alanları
+        // varsayılanlarla doldurmaktan ibaret. Alan silen ya da yeniden
+        // adlandıran bir sürümde buraya o sürüme özel adım eklenmeli.
+        if let Ok(defaults) = serde_json::to_value(Self::default()) {
+            Self::fill_missing(&mut value, &defaults);
+        }
+
+        if let Some(object) = value.as_object_mut() {
+            object.insert("schema_version".to_string(), SETTINGS_SCHEMA_VERSION.into());
+        }
+        value
+    }
+
+    /// Dosya bu uygulamadan yeni bir şemayla yazılmışsa o şemanın sürümü
+    fn newer_schema(content: &str) -> Option<u32> {
+        serde_json::from_str::<Value>(content)
+        .ok()?
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map(|version| version as u32)
+        .filter(|&version| version > SETTINGS_SCHEMA_VERSION)
+    }
+
+    /// `value` içinde olmayan alanları `defaults`'tan kopyala
+    fn fill_missing(value: &mut Value, defaults: &Value) {
+        let (Some(object), Some(default_object)) = (value.as_object_mut(), defaults.as_object()) else {
+            return;
+        };
+        for (key, default_value) in default_object {
+            match object.get_mut(key) {
+                Some(existing) => Self::fill_missing(existing, default_value),
+                None => {
+                    object.insert(key.clone(), default_value.clone());
+                }
+            }
+        }
+    }
+
+    /// Ayarları atomik olarak kaydet (geçici dosyaya yaz, sonra yerine taşı).
+    /// Proxy parolası içerdiği için dosya yalnızca kullanıcıya açıktır; daha
+    /// yeni bir sürümün yazdığı dosya, bilinmeyen alanları kaybolmasın diye
+    /// ezilmez.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&paths::settings_file())
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        if let Some(version) = std::fs::read_to_string(path).ok().as_deref().and_then(Self::newer_schema) {
+            anyhow::bail!(
+                "{} was written by a newer version (schema {}), not overwriting it",
+                path.display(),
+                version
+            );
+        }
+
+        // Eski geçici dosya başka izinlerle kalmış olabilir
+        let temp_path = path.with_extension("json.tmp");
+        let _ = std::fs::remove_file(&temp_path);
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&temp_path)?;
+        file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    pub fn is_held(&self, package_name: &str) -> bool {
+        self.held_packages.iter().any(|name| name == package_name)
+    }
+
+    /// Paketi hold listesine ekle ya da listeden çıkar
+    pub fn toggle_hold(&mut self, package_name: &str) {
+        if self.is_held(package_name) {
+            self.held_packages.retain(|name| name != package_name);
+        } else {
+            self.held_packages.push(package_name.to_string());
+            self.held_packages.sort();
+        }
+    }
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             package_list: PackageListSettings {
                 show_only_desktop_apps: false,
                 show_components_info: true,
@@ -82,6 +276,9 @@ impl Default for AppSettings {
                 use_disk_cache: true,
                 cache_size_mb: 1024,
                 cache_directory: "/var/cache/pisi/packages".to_string(),
+                use_bandwidth_limit: false,
+                bandwidth_limit_kbps: 0,
+                trim_after_operations: false,
             },
             proxy: ProxySettings {
                 use_proxy: false,
@@ -106,19 +303,21 @@ impl Default for AppSettings {
                 },
             ],
             system_tray_enabled: true,
+            held_packages: Vec::new(),
+            theme: Theme::System,
+            language: String::new(),
+            smart_collections: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct SettingsModalState {
     pub current_tab: SettingsTab,
-}
-
-impl Default for SettingsModalState {
-    fn default() -> Self {
-        Self {
-            current_tab: SettingsTab::General,
-        }
-    }
+    /// Depo tablosunda seçili satır
+    pub selected_repo: Option<usize>,
+    /// Depo işlemlerinden gelen hata ve uyarılar
+    pub repo_messages: Vec<String>,
+    /// Pencere bir önceki karede açık mıydı; yeniden açılınca depolar eşitlenir
+    pub visible: bool,
 }
@@ -0,0 +1,293 @@
+use eframe::egui;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::config::paths;
+
+/// Portal yoksa ayar dosyalarının yeniden okunma aralığı
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Masaüstünün koyu tema tercihini algıla, bilinmiyorsa None
+///
+/// Sıra: GTK_THEME ortam değişkeni (uygulamaya özel zorlama), xdg-desktop-portal
+/// color-scheme, GTK 4/3 settings.ini, KDE kdeglobals.
+pub fn detect_prefers_dark() -> Option<bool> {
+    from_environment()
+        .or_else(from_portal)
+        .or_else(from_gtk_settings)
+        .or_else(from_kdeglobals)
+}
+
+/// gdbus çağırmadan, yalnızca ortam değişkeni ve ayar dosyalarından algıla
+///
+/// Açılışta ilk kareyi portal yanıtı için bekletmemek içindir; portal sonucu
+/// `SystemThemeWatcher`'ın ilk okumasıyla gelir.
+pub fn detect_prefers_dark_fast() -> Option<bool> {
+    from_environment()
+        .or_else(from_gtk_settings)
+        .or_else(from_kdeglobals)
+}
+
+/// GTK_THEME=Adwaita:dark ya da Adwaita-dark
+fn from_environment() -> Option<bool> {
+    let theme = std::env::var("GTK_THEME").ok()?;
+    if theme.is_empty() {
+        return None;
+    }
+    Some(is_dark_name(&theme))
+}
+
+/// org.freedesktop.appearance color-scheme: 0 tercih yok, 1 koyu, 2 açık
+fn from_portal() -> Option<bool> {
+    let output = Command::new("gdbus")
+        .args([
+            "call", "--session", "--timeout", "1",
+            "--dest", "org.freedesktop.portal.Desktop",
+            "--object-path", "/org/freedesktop/portal/desktop",
+            "--method", "org.freedesktop.portal.Settings.Read",
+            "org.freedesktop.appearance", "color-scheme",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Çıktı: "(<<uint32 1>>,)"
+    color_scheme_prefers_dark(parse_uint32(&String::from_utf8_lossy(&output.stdout))?)
+}
+
+fn color_scheme_prefers_dark(value: u32) -> Option<bool> {
+    match value {
+        1 => Some(true),
+        2 => Some(false),
+        _ => None,
+    }
+}
+
+/// gdbus çıktısındaki ilk "uint32 N" değeri
+fn parse_uint32(text: &str) -> Option<u32> {
+    let value = text.split("uint32").nth(1)?;
+    let digits: String = value.trim_start().chars().take_while(|c| c.is_ascii_digit()).collect();
+    digits.parse().ok()
+}
+
+/// `gdbus monitor` satırından color-scheme değişikliği:
+/// "/org/freedesktop/portal/desktop: org.freedesktop.portal.Settings.SettingChanged
+/// ('org.freedesktop.appearance', 'color-scheme', <uint32 1>)"
+fn color_scheme_signal(line: &str) -> Option<u32> {
+    if !line.contains("Settings.SettingChanged") || !line.contains("'org.freedesktop.appearance', 'color-scheme'") {
+        return None;
+    }
+    parse_uint32(line)
+}
+
+/// Portalın SettingChanged sinyallerini dinleyen `gdbus monitor`
+fn spawn_portal_monitor() -> Option<Child> {
+    Command::new("gdbus")
+        .args([
+            "monitor", "--session",
+            "--dest", "org.freedesktop.portal.Desktop",
+            "--object-path", "/org/freedesktop/portal/desktop",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| println!("Could not start gdbus monitor: {}", e))
+        .ok()
+}
+
+fn from_gtk_settings() -> Option<bool> {
+    let config_home = paths::xdg_config_home();
+    ["gtk-4.0", "gtk-3.0"].iter().find_map(|version| {
+        let content = std::fs::read_to_string(config_home.join(version).join("settings.ini")).ok()?;
+
+        if let Some(prefer_dark) = ini_value(&content, "Settings", "gtk-application-prefer-dark-theme") {
+            if prefer_dark == "1" || prefer_dark.eq_ignore_ascii_case("true") {
+                return Some(true);
+            }
+        }
+        ini_value(&content, "Settings", "gtk-theme-name").map(is_dark_name)
+    })
+}
+
+fn from_kdeglobals() -> Option<bool> {
+    let content = std::fs::read_to_string(paths::xdg_config_home().join("kdeglobals")).ok()?;
+
+    // Pencere arka plan rengi en güvenilir bilgi: "BackgroundNormal=35,38,41"
+    if let Some(background) = ini_value(&content, "Colors:Window", "BackgroundNormal") {
+        let rgb: Vec<f32> = background
+            .split(',')
+            .filter_map(|part| part.trim().parse().ok())
+            .collect();
+        if rgb.len() >= 3 {
+            let luminance = 0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2];
+            return Some(luminance < 128.0);
+        }
+    }
+
+    ini_value(&content, "General", "ColorScheme").map(is_dark_name)
+}
+
+fn is_dark_name(name: &str) -> bool {
+    name.to_lowercase().contains("dark")
+}
+
+/// Basit INI okuyucu: `[section]` altındaki `key=value`
+fn ini_value<'a>(content: &'a str, section: &str, key: &str) -> Option<&'a str> {
+    let mut current = "";
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('[') && line.ends_with(']') {
+            current = &line[1..line.len() - 1];
+        } else if current == section {
+            if let Some((k, value)) = line.split_once('=') {
+                if k.trim() == key {
+                    return Some(value.trim());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Masaüstü tercihini arka planda izler, değişiklikleri kanala gönderir.
+/// Bırakılınca (drop) izleme durur; yalnızca System teması seçiliyken tutulur.
+///
+/// Portal varsa color-scheme `SettingChanged` sinyali beklenir; portal yoksa
+/// ya da monitor kapanırsa ayar dosyaları `POLL_INTERVAL` aralıkla okunur.
+pub struct SystemThemeWatcher {
+    receiver: Receiver<bool>,
+    stop: Arc<AtomicBool>,
+    /// Bırakılınca sonlandırılır, okuyan iş parçacığı da böylece biter
+    monitor: Arc<Mutex<Option<Child>>>,
+}
+
+impl SystemThemeWatcher {
+    /// `initial` bilinen son değer; yalnızca farklı değerler gönderilir.
+    /// İlk okuma beklemeden yapılır.
+    pub fn spawn(ctx: egui::Context, initial: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let monitor = Arc::new(Mutex::new(None));
+        let running_monitor = monitor.clone();
+
+        std::thread::spawn(move || {
+            let mut last = initial;
+            // Uygulama kapandıysa false döner
+            let mut notify = |prefers_dark: bool| {
+                if prefers_dark == last {
+                    return true;
+                }
+                println!("System theme changed: {}", if prefers_dark { "dark" } else { "light" });
+                last = prefers_dark;
+                ctx.request_repaint();
+                sender.send(prefers_dark).is_ok()
+            };
+
+            // `detect_prefers_dark` sırası; portal yanıt vermiyorsa sinyal de gelmez
+            let portal = from_portal();
+            let detected = from_environment()
+                .or(portal)
+                .or_else(from_gtk_settings)
+                .or_else(from_kdeglobals);
+            if !notify(detected.unwrap_or(false)) {
+                return;
+            }
+
+            let signals = portal.and_then(|_| {
+                let mut child = spawn_portal_monitor()?;
+                let signals = child.stdout.take();
+                let mut running = running_monitor.lock().ok()?;
+                // İzleyici bu arada bırakıldıysa monitor hemen kapatılır
+                if stopped.load(Ordering::Relaxed) {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return None;
+                }
+                *running = Some(child);
+                signals
+            });
+            if let Some(signals) = signals {
+                for line in BufReader::new(signals).lines().map_while(Result::ok) {
+                    let Some(value) = color_scheme_signal(&line) else {
+                        continue;
+                    };
+                    // 0: tercih yok, ayar dosyalarına bakılır
+                    let prefers_dark = from_environment()
+                        .or(color_scheme_prefers_dark(value))
+                        .or_else(from_gtk_settings)
+                        .or_else(from_kdeglobals)
+                        .unwrap_or(false);
+                    if !notify(prefers_dark) {
+                        return;
+                    }
+                }
+                if stopped.load(Ordering::Relaxed) {
+                    return;
+                }
+                println!("Portal monitor exited, watching theme settings files instead");
+            }
+
+            while !stopped.load(Ordering::Relaxed) {
+                std::thread::sleep(POLL_INTERVAL);
+                if !notify(detect_prefers_dark_fast().unwrap_or(false)) {
+                    break;
+                }
+            }
+        });
+
+        Self { receiver, stop, monitor }
+    }
+
+    /// Son değişikliği al (varsa)
+    pub fn poll(&self) -> Option<bool> {
+        self.receiver.try_iter().last()
+    }
+}
+
+impl Drop for SystemThemeWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(mut monitor) = self.monitor.lock().ok().and_then(|mut monitor| monitor.take()) {
+            let _ = monitor.kill();
+            let _ = monitor.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_color_scheme_from_portal_signal() {
+        let signal = "/org/freedesktop/portal/desktop: org.freedesktop.portal.Settings.SettingChanged \
+            ('org.freedesktop.appearance', 'color-scheme', <uint32 1>)";
+        assert_eq!(color_scheme_signal(signal), Some(1));
+        assert_eq!(color_scheme_signal(&signal.replace("uint32 1", "uint32 2")), Some(2));
+    }
+
+    #[test]
+    fn ignores_other_portal_settings() {
+        let accent = "/org/freedesktop/portal/desktop: org.freedesktop.portal.Settings.SettingChanged \
+            ('org.freedesktop.appearance', 'contrast', <uint32 1>)";
+        let gnome = "/org/freedesktop/portal/desktop: org.freedesktop.portal.Settings.SettingChanged \
+            ('org.gnome.desktop.interface', 'color-scheme', <'prefer-dark'>)";
+        assert_eq!(color_scheme_signal(accent), None);
+        assert_eq!(color_scheme_signal(gnome), None);
+        assert_eq!(color_scheme_signal("The name org.freedesktop.portal.Desktop is owned by :1.12"), None);
+    }
+
+    #[test]
+    fn maps_color_scheme_values() {
+        assert_eq!(parse_uint32("(<<uint32 2>>,)"), Some(2));
+        assert_eq!(color_scheme_prefers_dark(1), Some(true));
+        assert_eq!(color_scheme_prefers_dark(2), Some(false));
+        assert_eq!(color_scheme_prefers_dark(0), None);
+    }
+}
@@ -1,16 +1,28 @@
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+use crate::config::paths;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
 pub enum Theme {
     Light,
     Dark,
+    /// Masaüstünün açık/koyu tercihini izle
+    #[default]
+    System,
+    HighContrast,
+    /// Config dizinindeki themes/*.json dosyalarından biri
+    Custom(String),
 }
 
 impl Theme {
-    pub fn apply(&self, ctx: &egui::Context) {
+    pub const BUILT_IN: [Theme; 4] = [Theme::Light, Theme::Dark, Theme::System, Theme::HighContrast];
+
+    /// `System` temayı algılanan masaüstü tercihine göre Light/Dark'a çevir
+    pub fn resolve(&self, system_prefers_dark: bool) -> Theme {
         match self {
-            Theme::Light => ctx.set_visuals(egui::Visuals::light()),
-            Theme::Dark => ctx.set_visuals(egui::Visuals::dark()),
+            Theme::System if system_prefers_dark => Theme::Dark,
+            Theme::System => Theme::Light,
+            theme => theme.clone(),
         }
     }
 
@@ -18,13 +30,361 @@ impl Theme {
         match self {
             Theme::Light => "Light",
             Theme::Dark => "Dark",
+            Theme::System => "System",
+            Theme::HighContrast => "High Contrast",
+            Theme::Custom(name) => name,
         }
     }
 
+    pub fn icon(&self) -> &str {
+        match self {
+            Theme::Light => "☀️",
+            Theme::Dark => "🌙",
+            Theme::System => "🖥",
+            Theme::HighContrast => "◑",
+            Theme::Custom(_) => "🎨",
+        }
+    }
+
+    /// Başlıktaki düğme Light -> Dark -> System arasında döner
     pub fn next(&self) -> Self {
         match self {
             Theme::Light => Theme::Dark,
-            Theme::Dark => Theme::Light,
+            Theme::Dark => Theme::System,
+            _ => Theme::Light,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeBase {
+    #[default]
+    Light,
+    Dark,
+}
+
+/// Tema dosyası, `~/.config/pisi-package-manager/themes/*.json`
+///
+/// Verilmeyen alanlar `base` temasından gelir. Örnek:
+/// `{ "name": "Ocean", "base": "dark", "accent": "#1e90ff", "rounding": 8.0 }`
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeDefinition {
+    pub name: String,
+    pub base: ThemeBase,
+    /// "#rrggbb" biçiminde renkler
+    pub accent: Option<String>,
+    pub background: Option<String>,
+    pub panel: Option<String>,
+    pub text: Option<String>,
+    /// Düğme ve pencere kenarlıkları
+    pub border: Option<String>,
+    /// Köşe yuvarlaklığı (px)
+    pub rounding: Option<f32>,
+    /// Boşluk çarpanı, 1.0 varsayılan
+    pub spacing: Option<f32>,
+    pub font_size: Option<f32>,
+    pub heading_size: Option<f32>,
+    /// Paket kartı zemini ve durum renkleri (`ThemePalette`)
+    pub card: Option<String>,
+    pub installed: Option<String>,
+    pub update: Option<String>,
+    pub held: Option<String>,
+    pub success: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Bileşenlerin kullandığı renkler; egui varsayılanlarını elle seçmek yerine
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemePalette {
+    pub accent: egui::Color32,
+    pub card_fill: egui::Color32,
+    pub card_stroke: egui::Color32,
+    pub muted_text: egui::Color32,
+    /// Rozet yazısı, rozet zemini durum rengidir
+    pub badge_text: egui::Color32,
+    pub installed: egui::Color32,
+    pub update: egui::Color32,
+    pub held: egui::Color32,
+    pub success: egui::Color32,
+    pub error: egui::Color32,
+}
+
+impl ThemePalette {
+    /// Paket kartı çerçevesi
+    pub fn card_frame(&self, selected: bool) -> egui::Frame {
+        let stroke = if selected {
+            egui::Stroke::new(2.0, self.accent)
+        } else {
+            egui::Stroke::new(1.0, self.card_stroke)
+        };
+        egui::Frame::none()
+            .fill(self.card_fill)
+            .stroke(stroke)
+            .rounding(6.0)
+            .inner_margin(egui::Margin::same(8.0))
+    }
+
+    /// Renkli küçük etiket ("Installed", "Update", "Held")
+    pub fn badge(&self, ui: &mut egui::Ui, text: &str, colour: egui::Color32) -> egui::Response {
+        egui::Frame::none()
+            .fill(colour)
+            .rounding(4.0)
+            .inner_margin(egui::Margin::symmetric(6.0, 1.0))
+            .show(ui, |ui| {
+                ui.label(egui::RichText::new(text).small().strong().color(self.badge_text));
+            })
+            .response
+    }
+
+    /// Kaldırılabilir filtre etiketi; tıklanınca kapanır
+    pub fn chip(&self, ui: &mut egui::Ui, text: &str) -> egui::Response {
+        ui.add(
+            egui::Button::new(egui::RichText::new(format!("{}  ✖", text)).small())
+                .fill(self.card_fill)
+                .stroke(egui::Stroke::new(1.0, self.accent))
+                .rounding(10.0),
+        )
+    }
+}
+
+impl ThemeDefinition {
+    pub fn light() -> Self {
+        Self {
+            name: "Light".to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            name: "Dark".to_string(),
+            base: ThemeBase::Dark,
+            ..Self::default()
+        }
+    }
+
+    /// Siyah zemin, beyaz yazı, sarı vurgu ve kalın kenarlıklar
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "High Contrast".to_string(),
+            base: ThemeBase::Dark,
+            accent: Some("#ffff00".to_string()),
+            background: Some("#000000".to_string()),
+            panel: Some("#000000".to_string()),
+            text: Some("#ffffff".to_string()),
+            border: Some("#ffffff".to_string()),
+            rounding: Some(0.0),
+            spacing: None,
+            font_size: Some(16.0),
+            heading_size: Some(22.0),
+            card: Some("#000000".to_string()),
+            installed: Some("#00ff00".to_string()),
+            update: Some("#00ffff".to_string()),
+            held: Some("#ff8c00".to_string()),
+            success: Some("#00ff00".to_string()),
+            error: Some("#ff4040".to_string()),
+        }
+    }
+
+    /// Dosyadan yüklenen temadaki renkleri kontrol et
+    pub fn validate(&self) -> Result<(), String> {
+        let colours = [
+            ("accent", &self.accent),
+            ("background", &self.background),
+            ("panel", &self.panel),
+            ("text", &self.text),
+            ("border", &self.border),
+            ("card", &self.card),
+            ("installed", &self.installed),
+            ("update", &self.update),
+            ("held", &self.held),
+            ("success", &self.success),
+            ("error", &self.error),
+        ];
+        for (field, value) in colours {
+            if let Some(value) = value {
+                if parse_color(value).is_none() {
+                    return Err(format!("invalid colour for {}: {}", field, value));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_dark(&self) -> bool {
+        self.base == ThemeBase::Dark
+    }
+
+    /// Temel temanın renkleri, dosyada verilenlerle ezilir
+    pub fn palette(&self) -> ThemePalette {
+        let colour = |value: &Option<String>, fallback: egui::Color32| {
+            value.as_deref().and_then(parse_color).unwrap_or(fallback)
+        };
+
+        let base = match self.base {
+            ThemeBase::Light => ThemePalette {
+                accent: egui::Color32::from_rgb(0, 109, 200),
+                card_fill: egui::Color32::from_gray(250),
+                card_stroke: egui::Color32::from_gray(200),
+                muted_text: egui::Color32::from_gray(110),
+                badge_text: egui::Color32::WHITE,
+                installed: egui::Color32::from_rgb(46, 139, 87),
+                update: egui::Color32::from_rgb(0, 109, 200),
+                held: egui::Color32::from_rgb(200, 110, 0),
+                success: egui::Color32::from_rgb(60, 160, 60),
+                error: egui::Color32::from_rgb(200, 60, 60),
+            },
+            ThemeBase::Dark => ThemePalette {
+                accent: egui::Color32::from_rgb(90, 170, 255),
+                card_fill: egui::Color32::from_gray(35),
+                card_stroke: egui::Color32::from_gray(70),
+                muted_text: egui::Color32::from_gray(150),
+                badge_text: egui::Color32::BLACK,
+                installed: egui::Color32::from_rgb(110, 200, 120),
+                update: egui::Color32::from_rgb(90, 170, 255),
+                held: egui::Color32::from_rgb(240, 160, 60),
+                success: egui::Color32::from_rgb(110, 200, 120),
+                error: egui::Color32::from_rgb(240, 100, 100),
+            },
+        };
+
+        ThemePalette {
+            accent: colour(&self.accent, base.accent),
+            card_fill: colour(&self.card, colour(&self.panel, base.card_fill)),
+            card_stroke: colour(&self.border, base.card_stroke),
+            muted_text: colour(&self.text, base.muted_text),
+            badge_text: base.badge_text,
+            installed: colour(&self.installed, base.installed),
+            update: colour(&self.update, base.update),
+            held: colour(&self.held, base.held),
+            success: colour(&self.success, base.success),
+            error: colour(&self.error, base.error),
+        }
+    }
+
+    pub fn apply(&self, ctx: &egui::Context) {
+        let mut style = egui::Style {
+            visuals: match self.base {
+                ThemeBase::Light => egui::Visuals::light(),
+                ThemeBase::Dark => egui::Visuals::dark(),
+            },
+            ..egui::Style::default()
+        };
+
+        // Uygulamanın varsayılan aralıkları
+        let scale = self.spacing.unwrap_or(1.0).clamp(0.25, 4.0);
+        style.spacing.item_spacing = egui::vec2(8.0, 6.0) * scale;
+        style.spacing.button_padding = egui::vec2(12.0, 6.0) * scale;
+        style.spacing.indent *= scale;
+
+        let visuals = &mut style.visuals;
+        if let Some(accent) = self.accent.as_deref().and_then(parse_color) {
+            visuals.selection.bg_fill = accent;
+            visuals.hyperlink_color = accent;
+            visuals.widgets.hovered.bg_stroke.color = accent;
+            visuals.widgets.active.bg_stroke.color = accent;
+        }
+        if let Some(background) = self.background.as_deref().and_then(parse_color) {
+            visuals.window_fill = background;
+            visuals.extreme_bg_color = background;
+            visuals.faint_bg_color = background;
+        }
+        if let Some(panel) = self.panel.as_deref().and_then(parse_color) {
+            visuals.panel_fill = panel;
         }
+        if let Some(text) = self.text.as_deref().and_then(parse_color) {
+            visuals.override_text_color = Some(text);
+        }
+        if let Some(border) = self.border.as_deref().and_then(parse_color) {
+            let stroke = egui::Stroke::new(1.5, border);
+            visuals.window_stroke = stroke;
+            visuals.widgets.noninteractive.bg_stroke = stroke;
+            visuals.widgets.inactive.bg_stroke = stroke;
+        }
+        if let Some(rounding) = self.rounding {
+            let rounding = egui::Rounding::same(rounding.max(0.0));
+            visuals.window_rounding = rounding;
+            visuals.menu_rounding = rounding;
+            visuals.widgets.noninteractive.rounding = rounding;
+            visuals.widgets.inactive.rounding = rounding;
+            visuals.widgets.hovered.rounding = rounding;
+            visuals.widgets.active.rounding = rounding;
+            visuals.widgets.open.rounding = rounding;
+        }
+
+        if let Some(size) = self.font_size {
+            for (text_style, font) in style.text_styles.iter_mut() {
+                font.size = match text_style {
+                    egui::TextStyle::Heading => font.size,
+                    egui::TextStyle::Small => size * 0.75,
+                    _ => size,
+                };
+            }
+        }
+        if let Some(size) = self.heading_size {
+            if let Some(font) = style.text_styles.get_mut(&egui::TextStyle::Heading) {
+                font.size = size;
+            }
+        }
+
+        ctx.set_style(style);
+    }
+}
+
+/// Config dizinindeki tema dosyalarını yükle; hatalı dosyalar için mesaj döner
+pub fn load_user_themes() -> (Vec<ThemeDefinition>, Vec<String>) {
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+
+    let Ok(entries) = std::fs::read_dir(paths::themes_dir()) else {
+        return (themes, errors);
+    };
+    let mut files: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    for path in files {
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<ThemeDefinition>(&content).map_err(|e| e.to_string()));
+
+        match parsed {
+            Ok(mut theme) => {
+                if theme.name.trim().is_empty() {
+                    theme.name = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                }
+                match theme.validate() {
+                    Ok(()) => themes.push(theme),
+                    Err(e) => errors.push(format!("{}: {}", file_name, e)),
+                }
+            }
+            Err(e) => errors.push(format!("{}: {}", file_name, e)),
+        }
+    }
+
+    for error in &errors {
+        println!("Theme file skipped: {}", error);
+    }
+    (themes, errors)
+}
+
+/// "#rrggbb" ya da "#rrggbbaa"
+pub fn parse_color(value: &str) -> Option<egui::Color32> {
+    let hex = value.trim().strip_prefix('#')?;
+    if !hex.is_ascii() {
+        return None;
+    }
+    let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+
+    match hex.len() {
+        6 => Some(egui::Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?)),
+        8 => Some(egui::Color32::from_rgba_unmultiplied(channel(0)?, channel(2)?, channel(4)?, channel(6)?)),
+        _ => None,
     }
 }
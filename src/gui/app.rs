@@ -1,11 +1,40 @@
 use eframe::egui;
-use crate::config::{AppSettings, Theme};
-use crate::backend::xml_parser::{XmlParser, PackageInfo, Component, Source}; // Component ve Source'u da import et
+use crate::{i18n, t, tn};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use crate::config::{self, AppSettings, Theme, ThemeDefinition, ThemePalette};
+use crate::config::system_theme::{self, SystemThemeWatcher};
+use crate::backend::package_manager::{PackageManager, PackageOperation};
+use crate::backend::db_lock::DbLock;
+use crate::backend::repository::{RepoOperation, RepositoryManager};
+use crate::backend::downloader::{DownloadOptions, DownloadOutcome, Downloader};
+use crate::backend::pisi_conf::PisiConf;
+use crate::backend::cache::{format_size, CacheManager};
+use crate::backend::local_package::LocalPackage;
+use crate::backend::search::SearchMode;
+use crate::backend::search_index::SearchIndex;
+use crate::backend::provides::{InstalledFiles, ProviderSearch};
+use crate::backend::filter::PackageFilter;
+use crate::backend::collections::SmartCollection;
+use crate::backend::catalog::{PackageCatalog, PackageId};
+use crate::backend::xml_parser::{LoadProgress, XmlParser, PackageInfo, Source}; // Source'u da import et
 use crate::gui::components::{Sidebar, PackageGrid, PackageDetails};
 use crate::gui::components::settings_modal;
+use crate::gui::components::local_package_dialog::LocalPackageDialog;
+use crate::gui::components::lock_dialog::{LockDialog, LockWait, LOCK_WAIT_TIMEOUT};
+use crate::gui::components::add_repo_dialog::AddRepoDialog;
+use crate::gui::components::pisi_conf_dialog::{PisiConfDialog, PisiConfPreview};
+use crate::gui::components::cache_dialog::CacheDialog;
+use crate::gui::components::save_collection_dialog::SaveCollectionDialog;
 use crate::config::SettingsModalState;
 use crate::gui::events::{AppEvent, EventManager};
 use crate::gui::image_loader::ImageLoader;
+use crate::gui::file_dialog;
 
 #[derive(PartialEq)]
 pub enum AppView {
@@ -13,24 +42,107 @@ pub enum AppView {
     PackageList,
 }
 
+/// Provides aramasında yazmayı bitirmek için beklenen süre; dosya listesi büyük olduğu için her tuşta aranmaz
+const PROVIDES_SEARCH_DELAY: Duration = Duration::from_millis(300);
+
+/// Arka planda okunan paketler ve onlardan kurulan arama indeksi
+pub type LoadedPackages = anyhow::Result<(Vec<PackageInfo>, SearchIndex)>;
+
 pub struct PackageManagerApp {
-    pub current_theme: Theme,
     pub settings: AppSettings,
+    /// Masaüstünün algılanan koyu tema tercihi (Theme::System için)
+    pub system_prefers_dark: bool,
+    /// Yalnızca tema masaüstünü izlerken çalışır, bkz. `update_system_theme_watcher`
+    system_theme_watcher: Option<SystemThemeWatcher>,
+    /// themes/*.json dosyalarından yüklenen temalar
+    pub user_themes: Vec<ThemeDefinition>,
+    pub theme_errors: Vec<String>,
+    /// Son uygulanan tema, her karede stil kurmamak için
+    applied_theme: Option<ThemeDefinition>,
+    /// Etkin temanın bileşen renkleri
+    pub palette: ThemePalette,
+    /// Ayarlar son kayıttan beri değişti mi; `mark_settings_changed` ile işaretlenir
+    settings_dirty: bool,
+    /// `settings.repositories` diskten ya da pisi'den mi geldi; ilk çalıştırmada
+    /// varsayılan listedir ve fark hesaplamaya uygun değildir
+    repositories_known: bool,
     pub show_settings: bool,
     pub current_view: AppView,
     
     // Data
-    pub packages: Vec<PackageInfo>,
-    pub components: Vec<Component>,
+    /// Paketler, indeksleri ve kurulum durumları
+    pub catalog: PackageCatalog,
     pub selected_component: String,
     pub selected_category: String,
-    pub selected_package: Option<PackageInfo>,
+    pub selected_package: Option<PackageId>,
+    
+    // Arama
+    pub search_query: String,
+    /// Sorgu boş değilse sıralanmış sonuçlar
+    pub search_results: Option<Vec<PackageId>>,
+    pub search_index: SearchIndex,
+    pub search_mode: SearchMode,
+    /// Provides aramasında son değişiklik; `PROVIDES_SEARCH_DELAY` sonra aranır
+    search_typed_at: Option<Instant>,
+    /// Provides aramasında paket adı -> eşleşen girdi
+    pub provider_matches: HashMap<String, String>,
+    /// Kurulu paketlerin dosya listeleri, ilk Provides aramasında yüklenir
+    pub installed_files: Option<InstalledFiles>,
+    pub installed_files_loading: Option<Receiver<InstalledFiles>>,
+    /// Paket listesinin filtre çubuğu ve sıralaması
+    pub package_filter: PackageFilter,
+    /// Paket listesinde gösterilen paketler, sırasıyla; `invalidate_visible_packages`
+    /// sonrası bir kez yeniden kurulur
+    pub visible_packages: Vec<PackageId>,
+    /// `visible_packages` kimliği; kaydırma çapası liste değişimini bununla anlar
+    pub visible_packages_key: u64,
+    visible_packages_stale: bool,
+    /// `settings.smart_collections` ile aynı sırada paket sayıları
+    pub collection_counts: Vec<usize>,
+    
+    // Paket durumları
+    pub last_update_check: Option<Instant>,
+    /// pisi çağıran arka plan işleri sonuçlarını buraya gönderir; her karede
+    /// olay kuyruğuna aktarılır
+    worker_sender: Sender<AppEvent>,
+    worker_events: Receiver<AppEvent>,
+    refreshing_states: bool,
+    /// Kurulu paket listesi en az bir kez başarıyla okundu mu; okunmadan
+    /// "kurulu değil" sayılan her şey yanlış olur
+    pub installed_states_loaded: bool,
+    checking_updates: bool,
+    /// Zamanlanmış kontrol bitince otomatik güncelleme başlatılsın mı
+    auto_update_after_check: bool,
+    
+    // Yerel .pisi kurulumu
+    pub local_package: Option<LocalPackage>,
+    pub local_package_message: Option<String>,
+    
+    // Paket işlemleri
+    /// Süren işlem; bitene kadar yenisi başlatılmaz
+    pub running_operation: Option<PackageOperation>,
+    /// Süren depo işlemi; bu sırada Repos sekmesi kilitlidir
+    pub running_repo_operation: Option<RepoOperation>,
+    /// İşlem sonrası önbellek kırpma sürüyor
+    pub trimming_cache: bool,
+    pub lock_wait: Option<LockWait>,
+    pub status_message: Option<String>,
+    pub index_refresh: Option<Receiver<Vec<String>>>,
+    
+    // Arka planda index yükleme
+    pub package_loading: Option<Receiver<LoadedPackages>>,
+    pub load_progress: Arc<LoadProgress>,
+    pub packages_loaded: bool,
     
     // UI State
     pub sidebar: Sidebar,
     pub package_grid: PackageGrid,
     pub package_details: PackageDetails,
     pub settings_modal: SettingsModalState,
+    pub add_repo_dialog: Option<AddRepoDialog>,
+    pub pisi_conf_preview: Option<PisiConfPreview>,
+    pub cache_dialog: Option<CacheDialog>,
+    pub save_collection_dialog: Option<SaveCollectionDialog>,
     
     // Event system
     pub event_manager: EventManager,
@@ -45,33 +157,651 @@ impl PackageManagerApp {
         Self::setup_fonts(&cc.egui_ctx);
         
         // Initialize with default data (will be replaced with XML parsing)
-        let packages = Vec::new();
-        let components = Vec::new();
+        
+        // Kayıtlı ayarlar; ilk çalıştırmada dosya yoktur
+        let (stored_settings, settings_warning) = AppSettings::load();
+        let first_run = stored_settings.is_none();
+        let settings = stored_settings.unwrap_or_default();
+        i18n::set_language(&settings.language);
+        
+        // Portal sorgusu ilk kareyi bekletmesin; kesin sonuç izleyiciden gelir
+        let system_prefers_dark = system_theme::detect_prefers_dark_fast().unwrap_or(false);
+        let (user_themes, theme_errors) = config::load_user_themes();
+        
+        let (worker_sender, worker_events) = mpsc::channel();
         
         let mut app = Self {
-            current_theme: Theme::Light,
-            settings: AppSettings::default(),
+            settings,
+            system_prefers_dark,
+            system_theme_watcher: None,
+            user_themes,
+            theme_errors,
+            applied_theme: None,
+            palette: ThemeDefinition::light().palette(),
+            // İlk çalıştırmada dosya oluşsun
+            settings_dirty: first_run,
+            repositories_known: !first_run,
             show_settings: false,
             current_view: AppView::Welcome,
-            packages,
-            components,
+            catalog: PackageCatalog::default(),
             selected_component: "All".to_string(),
-            selected_category: "Installed".to_string(),
+            selected_category: "Explorer".to_string(),
             selected_package: None,
-            sidebar: Sidebar::default(),
-            package_grid: PackageGrid::default(),
-            package_details: PackageDetails::default(),
+            search_query: String::new(),
+            search_typed_at: None,
+            search_results: None,
+            search_index: SearchIndex::default(),
+            search_mode: SearchMode::Names,
+            provider_matches: HashMap::new(),
+            installed_files: None,
+            installed_files_loading: None,
+            package_filter: PackageFilter::default(),
+            visible_packages: Vec::new(),
+            visible_packages_key: 0,
+            visible_packages_stale: true,
+            collection_counts: Vec::new(),
+            last_update_check: None,
+            worker_sender,
+            worker_events,
+            refreshing_states: false,
+            installed_states_loaded: false,
+            checking_updates: false,
+            auto_update_after_check: false,
+            running_operation: None,
+            running_repo_operation: None,
+            trimming_cache: false,
+            local_package: None,
+            local_package_message: None,
+            lock_wait: None,
+            status_message: settings_warning,
+            index_refresh: None,
+            package_loading: None,
+            load_progress: Arc::new(LoadProgress::default()),
+            packages_loaded: false,
+            sidebar: Sidebar,
+            package_grid: PackageGrid,
+            package_details: PackageDetails,
             settings_modal: SettingsModalState::default(),
+            add_repo_dialog: None,
+            pisi_conf_preview: None,
+            cache_dialog: None,
+            save_collection_dialog: None,
             event_manager: EventManager::new(),
             image_loader: ImageLoader::new(),
         };
         
+        // Temayı ilk kareden önce uygula, yoksa açılışta yanlış tema görünür
+        app.apply_theme(&cc.egui_ctx);
+        
         // Logoları yükle
         app.load_images(&cc.egui_ctx);
         
+        // İlk çalıştırmada proxy ve önbellek ayarlarını pisi.conf'tan al
+        if first_run {
+            app.load_pisi_conf();
+        }
+        
+        // Depo listesini ve önceliklerini pisi'den al
+        app.sync_repositories();
+        
+        // Kurulu paketleri ve güncellemeleri al
+        app.refresh_package_states();
+        
         app
     }
 
+    /// .pisi dosyasını aç ve önizleme penceresini göster
+    pub fn open_local_package(&mut self, path: &Path) {
+        match LocalPackage::open(path) {
+            Ok(local) => {
+                self.local_package = Some(local);
+                self.local_package_message = None;
+            }
+            Err(e) => {
+                self.local_package = None;
+                self.local_package_message = Some(t!("Could not open package: {}", e));
+            }
+        }
+    }
+
+    /// Pencereye sürüklenip bırakılan .pisi dosyalarını yakala
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped = ctx.input(|i| {
+            i.raw.dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .find(|path| LocalPackage::is_pisi_file(path))
+        });
+        
+        if let Some(path) = dropped {
+            self.event_manager.push(AppEvent::OpenLocalPackage(path));
+        }
+    }
+
+    /// Kurulu paket listesini arka planda yenile ve güncellemeleri kontrol et;
+    /// sonuç `AppEvent::PackageStatesLoaded` ile gelir
+    pub fn refresh_package_states(&mut self) {
+        if !self.refreshing_states {
+            self.refreshing_states = true;
+            let sender = self.worker_sender.clone();
+            std::thread::spawn(move || {
+                let installed = PackageManager::get_installed_packages().map_err(|e| e.to_string());
+                let versions = PackageManager::get_installed_versions().map_err(|e| e.to_string());
+                let _ = sender.send(AppEvent::PackageStatesLoaded { installed, versions });
+            });
+        }
+        
+        if self.settings.update.check_updates {
+            self.check_for_updates();
+        }
+    }
+
+    /// `pisi list-upgrades` ile güncellemeleri arka planda kontrol et;
+    /// sonuç `AppEvent::UpdatesChecked` ile gelir
+    pub fn check_for_updates(&mut self) {
+        self.last_update_check = Some(Instant::now());
+        if self.checking_updates {
+            return;
+        }
+        
+        self.checking_updates = true;
+        let sender = self.worker_sender.clone();
+        std::thread::spawn(move || {
+            let upgradable = PackageManager::list_upgradable_packages().map_err(|e| e.to_string());
+            let _ = sender.send(AppEvent::UpdatesChecked(upgradable));
+        });
+    }
+
+    /// Arka plan işlerinin sonuçlarını olay kuyruğuna aktar
+    fn poll_worker_events(&mut self, ctx: &egui::Context) {
+        for event in self.worker_events.try_iter() {
+            self.event_manager.push(event);
+        }
+        if self.refreshing_states
+            || self.checking_updates
+            || self.running_operation.is_some()
+            || self.running_repo_operation.is_some()
+            || self.trimming_cache
+        {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+    }
+
+    fn on_package_states_loaded(&mut self, installed: Result<Vec<String>, String>, versions: Result<HashMap<String, String>, String>) {
+        self.refreshing_states = false;
+        match installed {
+            Ok(installed) => {
+                self.catalog.set_installed(installed);
+                self.installed_states_loaded = true;
+            }
+            Err(e) => println!("Failed to get installed packages: {}", e),
+        }
+        match versions {
+            Ok(versions) => self.catalog.set_installed_versions(versions),
+            Err(e) => println!("Failed to read installed versions: {}", e),
+        }
+        self.invalidate_visible_packages();
+        
+        // Kurulu dosyalar değişmiş olabilir; sonraki Provides aramasında yeniden okunur
+        self.installed_files = None;
+        self.refresh_collection_counts();
+    }
+
+    fn on_updates_checked(&mut self, upgradable: Result<Vec<String>, String>) {
+        self.checking_updates = false;
+        match upgradable {
+            Ok(upgradable) => self.catalog.set_upgradable(upgradable),
+            Err(e) => println!("Failed to check updates: {}", e),
+        }
+        self.invalidate_visible_packages();
+        self.refresh_collection_counts();
+        
+        if std::mem::take(&mut self.auto_update_after_check) {
+            self.start_automatic_update();
+        }
+    }
+
+    /// Hold listesindeki paketler hariç bekleyen güncellemeler
+    pub fn pending_updates(&self) -> Vec<String> {
+        PackageManager::filter_held(self.catalog.upgradable_names().to_vec(), &self.settings.held_packages)
+    }
+
+    pub fn is_installed(&self, package_name: &str) -> bool {
+        self.catalog.is_installed(package_name)
+    }
+
+    pub fn has_update(&self, package_name: &str) -> bool {
+        !self.settings.is_held(package_name) && self.catalog.is_upgradable(package_name)
+    }
+
+    /// Arama, filtre, kategori ya da paket durumları değişince çağrılır
+    pub fn invalidate_visible_packages(&mut self) {
+        self.visible_packages_stale = true;
+    }
+
+    /// `visible_packages` eskidiyse yeniden kur; her karede tüm katalog süzülmesin diye
+    fn refresh_visible_packages(&mut self) {
+        if !std::mem::take(&mut self.visible_packages_stale) {
+            return;
+        }
+        
+        // Arama sonuçları sıralı gelir, kategori ve component filtresi uygulanmaz
+        let packages = match &self.search_results {
+            Some(results) => results.iter().filter_map(|&id| self.catalog.get(id)).collect(),
+            None => self.browse_packages(),
+        };
+        let visible: Vec<PackageId> = self.package_filter
+            .apply(packages, |name| self.is_installed(name), |name| self.has_update(name))
+            .into_iter()
+            .filter_map(|package| self.catalog.id(&package.name))
+            .collect();
+        
+        let mut hasher = DefaultHasher::new();
+        visible.hash(&mut hasher);
+        self.visible_packages_key = hasher.finish();
+        self.visible_packages = visible;
+    }
+
+    /// Seçili kategori ve component'teki paketler, katalog sırasıyla
+    pub fn browse_packages(&self) -> Vec<&PackageInfo> {
+        let component = self.selected_component.as_str();
+        let ids: Vec<PackageId> = match self.selected_category.as_str() {
+            "Installed" => self.catalog.installed().to_vec(),
+            "Updates" => self.pending_updates().iter().filter_map(|name| self.catalog.id(name)).collect(),
+            // Tüm paketlerde component indeksi doğrudan kullanılır
+            _ if component != "All" => return self.catalog
+                .in_component(component)
+                .iter()
+                .filter_map(|&id| self.catalog.get(id))
+                .collect(),
+            _ => self.catalog.ids().collect(),
+        };
+        
+        ids.into_iter()
+            .filter_map(|id| self.catalog.get(id))
+            .filter(|package| component == "All" || &*package.part_of == component)
+            .collect()
+    }
+
+    /// Detay panelinde gösterilen paket
+    pub fn selected_package(&self) -> Option<&PackageInfo> {
+        self.selected_package.and_then(|id| self.catalog.get(id))
+    }
+
+    /// Ekranda kullanılan tema; System masaüstü tercihine çözülür
+    pub fn active_theme(&self) -> Theme {
+        self.settings.theme.resolve(self.system_prefers_dark)
+    }
+
+    /// Etkin temanın tanımı; silinmiş özel tema yerine masaüstü tercihi kullanılır
+    pub fn theme_definition(&self) -> ThemeDefinition {
+        match self.active_theme() {
+            Theme::Light => ThemeDefinition::light(),
+            Theme::Dark => ThemeDefinition::dark(),
+            Theme::HighContrast => ThemeDefinition::high_contrast(),
+            Theme::Custom(name) => self
+                .user_themes
+                .iter()
+                .find(|theme| theme.name == name)
+                .cloned()
+                .unwrap_or_else(|| Self::system_definition(self.system_prefers_dark)),
+            Theme::System => Self::system_definition(self.system_prefers_dark),
+        }
+    }
+
+    /// Tema masaüstü tercihine bağlı mı: System ya da bulunamayan özel tema
+    fn follows_system_theme(&self) -> bool {
+        match &self.settings.theme {
+            Theme::System => true,
+            Theme::Custom(name) => !self.user_themes.iter().any(|theme| &theme.name == name),
+            _ => false,
+        }
+    }
+
+    /// Masaüstü izleyicisini yalnızca gerektiğinde çalıştır, gelen değişikliği al
+    fn update_system_theme_watcher(&mut self, ctx: &egui::Context) {
+        if !self.follows_system_theme() {
+            self.system_theme_watcher = None;
+            return;
+        }
+        let watcher = self
+            .system_theme_watcher
+            .get_or_insert_with(|| SystemThemeWatcher::spawn(ctx.clone(), self.system_prefers_dark));
+        if let Some(prefers_dark) = watcher.poll() {
+            self.system_prefers_dark = prefers_dark;
+        }
+    }
+
+    fn system_definition(prefers_dark: bool) -> ThemeDefinition {
+        if prefers_dark {
+            ThemeDefinition::dark()
+        } else {
+            ThemeDefinition::light()
+        }
+    }
+
+    /// Tema değiştiyse stili yeniden kur
+    fn apply_theme(&mut self, ctx: &egui::Context) {
+        let definition = self.theme_definition();
+        if self.applied_theme.as_ref() != Some(&definition) {
+            definition.apply(ctx);
+            self.palette = definition.palette();
+            self.applied_theme = Some(definition);
+        }
+    }
+
+    /// Tema dosyalarını yeniden oku
+    pub fn reload_user_themes(&mut self) {
+        let (themes, errors) = config::load_user_themes();
+        self.user_themes = themes;
+        self.theme_errors = errors;
+        // Aynı isimli tema değişmiş olabilir
+        self.applied_theme = None;
+    }
+
+    /// Ayarlar değişti, karenin sonunda diske yazılacak
+    pub fn mark_settings_changed(&mut self) {
+        self.settings_dirty = true;
+    }
+
+    /// Ayarlar değiştiyse diske yaz
+    fn save_settings_if_changed(&mut self) {
+        if !self.settings_dirty {
+            return;
+        }
+        // Başarısız olsa da her karede tekrar denenmez, sonraki değişiklikte denenir
+        self.settings_dirty = false;
+        if let Err(e) = self.settings.save() {
+            self.status_message = Some(t!("Could not save settings: {}", e));
+        }
+    }
+
+    fn load_pisi_conf(&mut self) {
+        match PisiConf::load() {
+            Ok(conf) => {
+                conf.read_settings(&mut self.settings.proxy, &mut self.settings.cache);
+                self.mark_settings_changed();
+            }
+            Err(e) => println!("Using default proxy/cache settings: {}", e),
+        }
+    }
+
+    /// Ayarların pisi.conf'a nasıl yansıyacağını göster
+    pub fn preview_pisi_conf(&mut self) {
+        match PisiConfPreview::from_app(self) {
+            Ok(preview) => self.pisi_conf_preview = Some(preview),
+            Err(e) => self.status_message = Some(e),
+        }
+    }
+
+    /// Depo listesini pisi'den yükle, CLI ile yapılmış değişiklikleri bildir
+    ///
+    /// Karşılaştırma son kaydedilen ya da pisi'den okunan listeyle yapılır.
+    /// Farksız bir eşitleme önceki uyarıları silmez; açılışta bulunanlar Repos
+    /// sekmesi açılınca da görünür.
+    pub fn sync_repositories(&mut self) {
+        // Süren işlem bitince zaten eşitlenir; yarım durum fark sanılmasın
+        if self.running_repo_operation.is_some() {
+            return;
+        }
+        match RepositoryManager::list_repositories() {
+            Ok(actual) => {
+                let drift = if self.repositories_known {
+                    RepositoryManager::find_drift(&self.settings.repositories, &actual)
+                } else {
+                    Vec::new()
+                };
+                self.repositories_known = true;
+                if !drift.is_empty() {
+                    self.settings_modal.repo_messages = drift.iter().map(|d| d.to_string()).collect();
+                    self.status_message = Some(tn!(
+                        "{n} repository change was made outside the app, see Settings",
+                        "{n} repository changes were made outside the app, see Settings",
+                        drift.len()
+                    ));
+                }
+                if self.settings.repositories != actual {
+                    self.settings.repositories = actual;
+                    self.mark_settings_changed();
+                }
+            }
+            Err(e) => {
+                self.settings_modal.repo_messages = vec![t!("Could not read repositories: {}", e)];
+            }
+        }
+    }
+
+    /// Depo işlemini arka planda başlat; sonuç `AppEvent::RepoOperationFinished` ile gelir
+    pub fn start_repo_operation(&mut self, operation: RepoOperation) {
+        if self.running_repo_operation.is_some() {
+            return;
+        }
+        self.running_repo_operation = Some(operation.clone());
+        
+        let sender = self.worker_sender.clone();
+        std::thread::spawn(move || {
+            let result = operation.run().map_err(|e| e.to_string());
+            let _ = sender.send(AppEvent::RepoOperationFinished { operation, result });
+        });
+    }
+
+    fn on_repo_operation_finished(&mut self, operation: RepoOperation, result: Result<(), String>) {
+        self.running_repo_operation = None;
+        if let Ok(()) = result {
+            operation.apply(&mut self.settings.repositories);
+            self.mark_settings_changed();
+            self.settings_modal.selected_repo = match &operation {
+                RepoOperation::Remove(_) => None,
+                RepoOperation::Move { repo, .. } => self.settings.repositories.iter().position(|r| r.name == repo.name),
+                _ => self.settings_modal.selected_repo,
+            };
+        }
+        
+        // Başarısız olsa bile pisi'nin son durumunu göster
+        self.sync_repositories();
+        match result {
+            Ok(()) => self.reload_packages(),
+            Err(e) => self.settings_modal.repo_messages.push(format!("{}: {}", operation.description(), e)),
+        }
+    }
+
+    /// Etkin depoların index'lerini proxy ayarlarıyla arka planda indir
+    pub fn start_index_refresh(&mut self) {
+        if self.index_refresh.is_some() {
+            return;
+        }
+        
+        let repositories: Vec<_> = self.settings.repositories
+            .iter()
+            .filter(|repo| repo.enabled)
+            .cloned()
+            .collect();
+        let options = DownloadOptions::from_settings(&self.settings.proxy, &self.settings.cache);
+        let (sender, receiver) = mpsc::channel();
+        
+        self.status_message = Some(t!("Refreshing repository indexes..."));
+        self.index_refresh = Some(receiver);
+        
+        std::thread::spawn(move || {
+            let downloader = Downloader::new(options);
+            let report = repositories
+                .iter()
+                .map(|repo| match RepositoryManager::refresh_index(repo, &downloader) {
+                    Ok(DownloadOutcome::Downloaded(bytes)) => {
+                        t!("{}: updated ({} KB)", repo.name, bytes / 1024)
+                    }
+                    Ok(DownloadOutcome::NotModified) => t!("{}: up to date", repo.name),
+                    Err(e) => format!("{}: {}", repo.name, e),
+                })
+                .collect();
+            let _ = sender.send(report);
+        });
+    }
+
+    fn poll_index_refresh(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.index_refresh else {
+            return;
+        };
+        
+        match receiver.try_recv() {
+            Ok(report) => {
+                self.index_refresh = None;
+                self.status_message = Some(report.join(" • "));
+                self.reload_packages();
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(250));
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.index_refresh = None;
+                self.status_message = Some(t!("Index refresh was interrupted"));
+            }
+        }
+    }
+
+    /// Paket işlemini başlat; veritabanı kilitliyse kullanıcıya sor
+    pub fn start_operation(&mut self, operation: PackageOperation) {
+        if let Some(running) = &self.running_operation {
+            self.status_message = Some(t!("Wait for {} to finish", running.description()));
+            return;
+        }
+        if let Some(holder) = DbLock::holder() {
+            println!("Pisi database locked by {}, deferring: {}", holder, operation.description());
+            self.lock_wait = Some(LockWait::new(operation, holder));
+            return;
+        }
+        
+        self.execute_operation(operation);
+    }
+
+    /// İşlemi arka planda çalıştır; sonuç `AppEvent::OperationFinished` ile gelir
+    fn execute_operation(&mut self, operation: PackageOperation) {
+        self.status_message = Some(t!("{}: running...", operation.description()));
+        self.running_operation = Some(operation.clone());
+        
+        let bandwidth_limit = self.settings.cache.bandwidth_limit();
+        let sender = self.worker_sender.clone();
+        std::thread::spawn(move || {
+            let result = operation.run(bandwidth_limit).map_err(|e| e.to_string());
+            let _ = sender.send(AppEvent::OperationFinished { operation, result });
+        });
+    }
+
+    fn on_operation_finished(&mut self, operation: PackageOperation, result: Result<(), String>) {
+        self.running_operation = None;
+        match result {
+            Ok(()) => {
+                self.status_message = Some(t!("{}: done", operation.description()));
+                self.refresh_package_states();
+                self.start_cache_trim();
+            }
+            Err(e) => {
+                self.status_message = Some(format!("{}: {}", operation.description(), e));
+            }
+        }
+    }
+
+    /// Kullanıcı açtıysa önbelleği boyut sınırına indir; silme başlarken
+    /// `AppEvent::CacheTrimStarted` ile durum satırında gösterilir
+    fn start_cache_trim(&mut self) {
+        let cache = &self.settings.cache;
+        if !cache.trim_after_operations || !cache.use_disk_cache || cache.cache_size_mb == 0 || self.trimming_cache {
+            return;
+        }
+        self.trimming_cache = true;
+
+        let directory = PathBuf::from(&cache.cache_directory);
+        let limit_mb = cache.cache_size_mb;
+        let sender = self.worker_sender.clone();
+        std::thread::spawn(move || {
+            let result = CacheManager::over_limit(&directory, limit_mb).and_then(|files| {
+                if files.is_empty() {
+                    return Ok(0);
+                }
+                let size = files.iter().map(|file| file.size).sum();
+                let _ = sender.send(AppEvent::CacheTrimStarted { files: files.len(), size });
+                CacheManager::delete(&files)
+            });
+            let _ = sender.send(AppEvent::CacheTrimFinished(result.map_err(|e| e.to_string())));
+        });
+    }
+
+    fn on_cache_trim_finished(&mut self, result: Result<u64, String>) {
+        self.trimming_cache = false;
+        match result {
+            Ok(0) => {}
+            Ok(freed) => {
+                println!("Package cache trimmed, freed {}", format_size(freed));
+                self.status_message = Some(t!("Package cache trimmed, freed {}", format_size(freed)));
+            }
+            Err(e) => {
+                println!("Failed to trim package cache: {}", e);
+                self.status_message = Some(t!("Could not trim the package cache: {}", e));
+            }
+        }
+    }
+
+    /// Kilit beklenirken her karede durumu kontrol et
+    fn poll_lock_wait(&mut self, ctx: &egui::Context) {
+        let Some(lock_wait) = &mut self.lock_wait else {
+            return;
+        };
+        let Some(since) = lock_wait.waiting_since else {
+            return;
+        };
+        
+        match DbLock::holder() {
+            None => {
+                if let Some(lock_wait) = self.lock_wait.take() {
+                    self.execute_operation(lock_wait.operation);
+                }
+            }
+            Some(_) if since.elapsed() >= LOCK_WAIT_TIMEOUT => {
+                self.status_message = Some(t!(
+                    "{}: timed out waiting for {}",
+                    lock_wait.operation.description(),
+                    lock_wait.holder
+                ));
+                self.lock_wait = None;
+            }
+            Some(holder) => {
+                // Kilidi başka bir süreç devralmış olabilir
+                lock_wait.holder = holder;
+                ctx.request_repaint_after(Duration::from_millis(500));
+            }
+        }
+    }
+
+    /// Zamanlanmış güncelleme kontrolü ve otomatik güncelleme
+    fn run_scheduled_updates(&mut self) {
+        let update = &self.settings.update;
+        if !update.check_updates || update.update_check_interval == 0 {
+            return;
+        }
+        
+        let interval = Duration::from_secs(update.update_check_interval as u64 * 60);
+        let due = self
+            .last_update_check
+            .is_none_or(|last| last.elapsed() >= interval);
+        if !due {
+            return;
+        }
+        
+        self.check_for_updates();
+        self.auto_update_after_check = self.settings.update.install_updates_automatically;
+    }
+
+    /// Kontrol sonrası bekleyen güncellemeleri kur; kullanıcının başlattığı
+    /// işlemlerle aynı kuyruktan geçer (kilit penceresi, durum yenileme)
+    fn start_automatic_update(&mut self) {
+        if self.pending_updates().is_empty() || self.running_operation.is_some() || self.lock_wait.is_some() {
+            return;
+        }
+        
+        let held = self.settings.held_packages.clone();
+        self.event_manager.push(AppEvent::RunOperation(PackageOperation::UpdateAll { held }));
+    }
+
     /// Logoları yükle
     fn load_images(&mut self, ctx: &egui::Context) {
         // Mevcut çalışma dizinini al
@@ -123,11 +853,7 @@ impl PackageManagerApp {
         let fonts = egui::FontDefinitions::default();
         ctx.set_fonts(fonts);
         
-        // Stil ayarları - sistem temasına uyumlu
-        let mut style = (*ctx.style()).clone();
-        style.spacing.item_spacing = egui::vec2(8.0, 6.0);
-        style.spacing.button_padding = egui::vec2(12.0, 6.0);
-        ctx.set_style(style);
+        // Aralık ve yazı boyutları ThemeDefinition::apply içinde ayarlanır
     }
 
     fn handle_events(&mut self) {
@@ -136,21 +862,84 @@ impl PackageManagerApp {
                 AppEvent::CategorySelected(category) => {
                     self.selected_category = category;
                     self.current_view = AppView::PackageList;
+                    self.invalidate_visible_packages();
                 }
                 AppEvent::ComponentSelected(component) => {
                     self.selected_component = component;
                     self.current_view = AppView::PackageList;
+                    self.invalidate_visible_packages();
                 }
                 AppEvent::ShowSettings => {
                     self.show_settings = true;
                 }
+                AppEvent::SelectPackage(id) => {
+                    self.selected_package = Some(id);
+                }
+                AppEvent::TogglePackageHold(package_name) => {
+                    self.settings.toggle_hold(&package_name);
+                    self.mark_settings_changed();
+                    self.invalidate_visible_packages();
+                    self.refresh_collection_counts();
+                }
+                AppEvent::OpenLocalPackage(path) => {
+                    self.open_local_package(&path);
+                }
+                AppEvent::RunOperation(operation) => {
+                    self.start_operation(operation);
+                }
+                AppEvent::FilterChanged(filter) => {
+                    self.package_filter = filter;
+                    self.invalidate_visible_packages();
+                }
+                AppEvent::ApplyCollection(index) => {
+                    self.apply_collection(index);
+                }
+                AppEvent::SaveCollection => {
+                    self.save_collection_dialog = Some(SaveCollectionDialog::default());
+                }
+                AppEvent::DeleteCollection(index) => {
+                    if index < self.settings.smart_collections.len() {
+                        self.settings.smart_collections.remove(index);
+                        self.mark_settings_changed();
+                        self.refresh_collection_counts();
+                    }
+                }
+                AppEvent::ExportCollections => {
+                    self.export_collections();
+                }
+                AppEvent::ImportCollections => {
+                    self.import_collections();
+                }
+                AppEvent::PackageStatesLoaded { installed, versions } => {
+                    self.on_package_states_loaded(installed, versions);
+                }
+                AppEvent::UpdatesChecked(upgradable) => {
+                    self.on_updates_checked(upgradable);
+                }
+                AppEvent::OperationFinished { operation, result } => {
+                    self.on_operation_finished(operation, result);
+                }
+                AppEvent::RepoOperationFinished { operation, result } => {
+                    self.on_repo_operation_finished(operation, result);
+                }
+                AppEvent::CacheTrimStarted { files, size } => {
+                    self.status_message = Some(t!(
+                        "Trimming package cache to {} MB: removing {} old files ({})...",
+                        self.settings.cache.cache_size_mb,
+                        files,
+                        format_size(size)
+                    ));
+                }
+                AppEvent::CacheTrimFinished(result) => {
+                    self.on_cache_trim_finished(result);
+                }
             }
         }
     }
 
     /// Ana logo render - welcome screen için
     fn render_main_logo(&self, ui: &mut egui::Ui) {
-        let logo_name = if self.current_theme == Theme::Light {
+        let logo_name = if !self.theme_definition().is_dark() {
             "pisi_logo_light"
         } else {
             "pisi_logo_dark"
@@ -164,14 +953,14 @@ impl PackageManagerApp {
             ui.vertical_centered(|ui| {
                 ui.heading("🦊 Pisi GNU/Linux");
                 ui.add_space(10.0);
-                ui.label("Paket Yöneticisi");
+                ui.label(t!("Package Manager"));
             });
         }
     }
 
     /// Küçük logo render - header için
     fn render_small_logo(&self, ui: &mut egui::Ui, size: f32) {
-        let logo_name = if self.current_theme == Theme::Light {
+        let logo_name = if !self.theme_definition().is_dark() {
             "pisi_logo_dark" // Light temada dark logo daha görünür olur
         } else {
             "pisi_logo_light" // Dark temada light logo
@@ -212,108 +1001,340 @@ impl PackageManagerApp {
     fn render_quick_stats(&self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
             ui.vertical(|ui| {
-                ui.heading("6501");
-                ui.label("Toplam Paket");
+                ui.heading(self.catalog.len().to_string());
+                ui.label(t!("Total Packages"));
             });
             
             ui.add_space(30.0);
             
             ui.vertical(|ui| {
-                ui.heading("15");
-                ui.label("Güncelleme");
+                ui.heading(self.pending_updates().len().to_string());
+                ui.label(t!("Updates"));
             });
             
             ui.add_space(30.0);
             
             ui.vertical(|ui| {
-                ui.heading("3127");
-                ui.label("Kurulu Paket");
+                ui.heading(self.catalog.installed().len().to_string());
+                ui.label(t!("Installed Packages"));
             });
         });
     }
     
-    fn render_quick_actions(&self, ui: &mut egui::Ui) {
+    fn render_quick_actions(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
-            if ui.button("📦 Tüm Paketleri Görüntüle").clicked() {
+            if ui.button(t!("📦 Browse All Packages")).clicked() {
                 // Bu event'leri handle_events'te yakalayacağız
                 println!("Tüm paketler butonuna tıklandı");
             }
             
-            if ui.button("🔄 Güncellemeleri Kontrol Et").clicked() {
-                println!("Güncellemeler butonuna tıklandı");
+            if ui.button(t!("🔄 Check for Updates")).clicked() {
+                self.check_for_updates();
             }
         });
     }
-    pub fn load_packages_from_xml(&mut self) {
-        if !self.packages.is_empty() {
+    /// Etkin depoların index'lerini arka planda oku; ilerleme yükleme ekranında gösterilir
+    pub fn start_package_load(&mut self) {
+        if self.packages_loaded || self.package_loading.is_some() {
             return;
         }
 
         println!("Loading packages from local Pisi repository...");
         
-        match XmlParser::load_pisi_index() {
-            Ok(packages) => {
-                self.packages = packages;
-                self.components = XmlParser::parse_components(&self.packages);
+        let repositories: Vec<String> = self.settings.repositories
+            .iter()
+            .filter(|repo| repo.enabled)
+            .map(|repo| repo.name.clone())
+            .collect();
+        let progress = Arc::new(LoadProgress::default());
+        let (sender, receiver) = mpsc::channel();
+        
+        self.load_progress = progress.clone();
+        self.package_loading = Some(receiver);
+        
+        std::thread::spawn(move || {
+            let repositories: Vec<&str> = repositories.iter().map(String::as_str).collect();
+            let result = XmlParser::load_repository_indexes(&repositories, &progress).map(|packages| {
+                let search_index = SearchIndex::load_or_build(&packages, &repositories);
+                (packages, search_index)
+            });
+            let _ = sender.send(result);
+        });
+    }
+
+    fn poll_package_load(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.package_loading else {
+            return;
+        };
+        
+        match receiver.try_recv() {
+            Ok(result) => {
+                self.package_loading = None;
+                self.finish_package_load(result);
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                // İlerleme çubuğu akıcı görünsün
+                ctx.request_repaint_after(Duration::from_millis(50));
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.package_loading = None;
+                self.finish_package_load(Err(anyhow::anyhow!("Index loader stopped unexpectedly")));
+            }
+        }
+    }
+
+    fn finish_package_load(&mut self, result: LoadedPackages) {
+        self.packages_loaded = true;
+        
+        match result {
+            Ok((packages, search_index)) => {
+                self.catalog.replace_packages(packages);
+                self.search_index = search_index;
                 
                 println!("=== FINAL PACKAGE LOADING SUMMARY ===");
-                println!("Total packages loaded: {}", self.packages.len());
-                println!("Total components found: {}", self.components.len());
+                println!("Total packages loaded: {}", self.catalog.len());
+                println!("Total components found: {}", self.catalog.components().len());
                 
                 // Component'leri göster
                 println!("\nTop 10 components by package count:");
-                let mut sorted_components = self.components.clone();
-                sorted_components.sort_by(|a, b| b.package_count.cmp(&a.package_count));
+                let mut sorted_components = self.catalog.components().to_vec();
+                sorted_components.sort_by_key(|c| std::cmp::Reverse(c.package_count));
                 
                 for comp in sorted_components.iter().take(10) {
                     if comp.name != "All" {
                         println!("  - {}: {} packages", comp.name, comp.package_count);
                     }
                 }
-                
-                // İlk 3 paketi detaylı göster
-                println!("\nFirst 3 packages (with correct PartOf):");
-                for pkg in self.packages.iter().take(3) {
-                    println!("  - {}", pkg.name);
-                    println!("    Summary: {}", pkg.summary);
-                    println!("    Version: {}", pkg.version);
-                    println!("    PartOf: {}", pkg.part_of);
-                    println!("    License: {}", pkg.license);
-                    println!();
-                }
             }
             Err(e) => {
                 println!("Failed to load Pisi index: {}. Using mock data.", e);
                 self.create_mock_data();
+                self.search_index = SearchIndex::load_or_build(self.catalog.packages(), &[]);
+            }
+        }
+        
+        // Paket listesi değişti; açık aramayı ve koleksiyon sayılarını yenile
+        self.update_search();
+        self.refresh_collection_counts();
+    }
+
+    /// Arama sonuçlarını `search_query`'ye göre yeniden hesapla
+    pub fn update_search(&mut self) {
+        self.search_typed_at = None;
+        self.invalidate_visible_packages();
+        if self.search_query.trim().is_empty() {
+            self.search_results = None;
+            self.provider_matches.clear();
+            return;
+        }
+        if self.search_mode == SearchMode::Provides {
+            self.start_installed_files_load();
+        }
+        
+        let (results, provider_matches) = self.search_positions(&self.search_query, self.search_mode);
+        self.search_results = Some(results);
+        self.provider_matches = provider_matches;
+        self.current_view = AppView::PackageList;
+    }
+
+    /// Provides sorgusu yazılırken bekleme süresi dolunca aramayı çalıştır
+    fn poll_search_delay(&mut self, ctx: &egui::Context) {
+        let Some(typed_at) = self.search_typed_at else {
+            return;
+        };
+        let elapsed = typed_at.elapsed();
+        if elapsed >= PROVIDES_SEARCH_DELAY {
+            self.update_search();
+        } else {
+            ctx.request_repaint_after(PROVIDES_SEARCH_DELAY - elapsed);
+        }
+    }
+
+    /// Sorguya uyan paketler, en iyi eşleşme önce; Provides aramasında eşleşen girdiler de döner
+    fn search_positions(&self, query: &str, mode: SearchMode) -> (Vec<PackageId>, HashMap<String, String>) {
+        let mut provider_matches = HashMap::new();
+        let names: Vec<String> = match mode {
+            SearchMode::Names => self.search_index
+                .search(query)
+                .into_iter()
+                .map(|hit| hit.name)
+                .collect(),
+            SearchMode::Provides => {
+                let empty = InstalledFiles::default();
+                let installed = self.installed_files.as_ref().unwrap_or(&empty);
+                ProviderSearch::search(self.catalog.packages(), installed, query)
+                    .into_iter()
+                    .map(|hit| {
+                        provider_matches.insert(hit.name.clone(), hit.provided);
+                        hit.name
+                    })
+                    .collect()
+            }
+        };
+        let results = names.iter().filter_map(|name| self.catalog.id(name)).collect();
+        (results, provider_matches)
+    }
+
+    /// Koleksiyonun şu anki paketleri; sorgusu boşsa tüm paketler filtrelenir
+    pub fn collection_packages(&self, collection: &SmartCollection) -> Vec<&PackageInfo> {
+        let packages = if collection.query.trim().is_empty() {
+            self.catalog.packages().iter().collect()
+        } else {
+            let (results, _) = self.search_positions(&collection.query, collection.search_mode);
+            results.iter().filter_map(|&id| self.catalog.get(id)).collect()
+        };
+        collection.filter.apply(packages, |name| self.is_installed(name), |name| self.has_update(name))
+    }
+
+    /// Kenar çubuğundaki sayıları yenile; paketler, kurulu liste ya da koleksiyonlar değişince çağrılır
+    pub fn refresh_collection_counts(&mut self) {
+        if self.settings.smart_collections.iter().any(|c| c.search_mode == SearchMode::Provides) {
+            self.start_installed_files_load();
+        }
+        self.collection_counts = self.settings.smart_collections
+            .iter()
+            .map(|collection| self.collection_packages(collection).len())
+            .collect();
+    }
+
+    /// Ekrandaki arama ve filtreler bu koleksiyonla aynı mı
+    pub fn is_collection_active(&self, collection: &SmartCollection) -> bool {
+        self.search_query.trim() == collection.query.trim()
+            && (collection.query.trim().is_empty() || self.search_mode == collection.search_mode)
+            && self.package_filter == collection.filter
+            && self.current_view == AppView::PackageList
+    }
+
+    /// Koleksiyonun arama ve filtrelerini ekrana getir
+    fn apply_collection(&mut self, index: usize) {
+        let Some(collection) = self.settings.smart_collections.get(index).cloned() else {
+            return;
+        };
+        self.search_query = collection.query;
+        self.search_mode = collection.search_mode;
+        self.package_filter = collection.filter;
+        // Koleksiyon sayısı tüm paketler üzerinden hesaplanır
+        self.selected_category = "Explorer".to_string();
+        self.selected_component = "All".to_string();
+        self.update_search();
+        self.current_view = AppView::PackageList;
+    }
+
+    /// Ekrandaki arama ve filtreleri koleksiyon olarak kaydet; aynı ad varsa değiştirilir
+    pub fn save_collection(&mut self, name: &str) {
+        let collection = SmartCollection {
+            name: name.trim().to_string(),
+            query: self.search_query.trim().to_string(),
+            search_mode: self.search_mode,
+            filter: self.package_filter.clone(),
+        };
+        SmartCollection::upsert(&mut self.settings.smart_collections, collection);
+        self.mark_settings_changed();
+        self.refresh_collection_counts();
+    }
+
+    fn export_collections(&mut self) {
+        let Some(path) = file_dialog::save_json_file("smart-collections.json") else {
+            return;
+        };
+        if let Err(e) = SmartCollection::export(&self.settings.smart_collections, &path) {
+            self.status_message = Some(t!("Could not export collections: {}", e));
+        }
+    }
+
+    fn import_collections(&mut self) {
+        let Some(path) = file_dialog::pick_json_file() else {
+            return;
+        };
+        match SmartCollection::import(&path) {
+            Ok(collections) => {
+                let count = collections.len();
+                for collection in collections {
+                    SmartCollection::upsert(&mut self.settings.smart_collections, collection);
+                }
+                self.mark_settings_changed();
+                self.refresh_collection_counts();
+                self.status_message = Some(tn!("Imported {n} smart collection", "Imported {n} smart collections", count));
             }
+            Err(e) => self.status_message = Some(t!("Could not import collections: {}", e)),
         }
     }
 
+    /// Kurulu paketlerin files.xml'lerini arka planda oku
+    fn start_installed_files_load(&mut self) {
+        if self.installed_files.is_some() || self.installed_files_loading.is_some() {
+            return;
+        }
+        
+        let (sender, receiver) = mpsc::channel();
+        self.installed_files_loading = Some(receiver);
+        std::thread::spawn(move || {
+            let _ = sender.send(InstalledFiles::load());
+        });
+    }
+
+    fn poll_installed_files(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.installed_files_loading else {
+            return;
+        };
+        
+        match receiver.try_recv() {
+            Ok(files) => {
+                self.installed_files_loading = None;
+                self.installed_files = Some(files);
+                // Yükleme sırasında yazılan sorguyu dosyalarla tekrar ara
+                self.update_search();
+                self.refresh_collection_counts();
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(250));
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.installed_files_loading = None;
+                self.installed_files = Some(InstalledFiles::default());
+            }
+        }
+    }
+
+    /// Depo sırası ya da durumu değiştiğinde paketleri yeniden yükle
+    pub fn reload_packages(&mut self) {
+        // Kurulum durumları korunur, paketler yeniden okunur
+        self.catalog.replace_packages(Vec::new());
+        self.selected_package = None;
+        self.invalidate_visible_packages();
+        // Süren yükleme eski depo listesiyle başladı; sonucu beklenmez
+        self.package_loading = None;
+        self.packages_loaded = false;
+        self.start_package_load();
+    }
+
     /// Mock data fallback - sadece Pisi index yoksa
     fn create_mock_data(&mut self) {
         println!("Creating mock data for demonstration...");
         
-        self.packages = vec![
+        let packages = vec![
             PackageInfo {
                 name: "firefox".to_string(),
                 summary: "Mozilla Firefox Web Browser".to_string(),
                 description: "Fast and secure web browser".to_string(),
                 version: "115.0".to_string(),
                 release: 1,
-                license: "MPL-2.0".to_string(),
-                part_of: "web".to_string(),
+                license: "MPL-2.0".into(),
+                part_of: "web".into(),
                 package_size: 97_000_000,
                 installed_size: 245_000_000,
                 package_format: "1.0".to_string(),
                 distribution: "PisiLinux".to_string(),
                 distribution_release: "2.0".to_string(),
-                architecture: "x86_64".to_string(),
+                architecture: "x86_64".into(),
+                repository: "Stable".into(),
                 source: Some(Source {
                     name: "firefox".to_string(),
                     homepage: "https://www.mozilla.org/firefox/".to_string(),
                 }),
                 history: vec![],
                 dependencies: vec![],
+                provides: vec![],
             },
             PackageInfo {
                 name: "libreoffice".to_string(),
@@ -321,75 +1342,164 @@ impl PackageManagerApp {
                 description: "Complete office productivity suite".to_string(),
                 version: "7.5.0".to_string(),
                 release: 1,
-                license: "MPL-2.0".to_string(),
-                part_of: "office".to_string(),
+                license: "MPL-2.0".into(),
+                part_of: "office".into(),
                 package_size: 245_000_000,
                 installed_size: 512_000_000,
                 package_format: "1.0".to_string(),
                 distribution: "PisiLinux".to_string(),
                 distribution_release: "2.0".to_string(),
-                architecture: "x86_64".to_string(),
+                architecture: "x86_64".into(),
+                repository: "Stable".into(),
                 source: Some(Source {
                     name: "libreoffice".to_string(),
                     homepage: "https://www.libreoffice.org/".to_string(),
                 }),
                 history: vec![],
                 dependencies: vec![],
+                provides: vec![],
             },
         ];
         
-        self.components = XmlParser::parse_components(&self.packages);
+        self.catalog.replace_packages(packages);
     }
 }
 
 impl eframe::App for PackageManagerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Apply theme
-        self.current_theme.apply(ctx);
+        self.update_system_theme_watcher(ctx);
+        self.apply_theme(ctx);
         
         // Handle pending events
+        self.handle_dropped_files(ctx);
+        self.poll_worker_events(ctx);
         self.handle_events();
         
         // Load data if needed
-        self.load_packages_from_xml();
+        self.start_package_load();
+        self.poll_package_load(ctx);
+        
+        // Update check / automatic updates
+        self.run_scheduled_updates();
+        
+        // Database lock wait
+        self.poll_lock_wait(ctx);
+        
+        // Background index download
+        self.poll_index_refresh(ctx);
+        
+        // Installed file lists for provides search
+        self.poll_installed_files(ctx);
+        self.poll_search_delay(ctx);
+        self.refresh_visible_packages();
 
         // Top panel with header
         egui::TopBottomPanel::top("header").show(ctx, |ui| {
             self.render_header(ui);
         });
 
-        // Sidebar
-        egui::SidePanel::left("sidebar")
-            .resizable(false)
-            .min_width(250.0)
-            .show(ctx, |ui| {
-                self.sidebar.render(ui, &mut self.event_manager);
+        // Status bar
+        if let Some(message) = &self.status_message {
+            let mut dismissed = false;
+            egui::TopBottomPanel::bottom("status").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(message);
+                    if ui.small_button("✖").clicked() {
+                        dismissed = true;
+                    }
+                });
             });
+            if dismissed {
+                self.status_message = None;
+            }
+        }
 
-        // Package details panel
-        egui::SidePanel::right("details")
-            .resizable(false)
-            .min_width(300.0)
-            .show(ctx, |ui| {
-                let app_ref = &*self;
-                self.package_details.render(ui, app_ref);
+        if self.package_loading.is_some() {
+            // İlk kare boş kalmasın; index'ler okunurken ilerlemeyi göster
+            egui::CentralPanel::default().show(ctx, |ui| {
+                self.render_loading_screen(ui);
             });
+        } else {
+            // Sidebar
+            egui::SidePanel::left("sidebar")
+                .resizable(false)
+                .min_width(250.0)
+                .show(ctx, |ui| {
+                    let app_ref = &*self;
+                    let mut events = Vec::new();
+                    self.sidebar.render(ui, app_ref, &mut events);
+                    for event in events {
+                        self.event_manager.push(event);
+                    }
+                });
 
-        // Main content area
-        egui::CentralPanel::default().show(ctx, |ui| {
-            match self.current_view {
-                AppView::Welcome => self.render_welcome_screen(ui),
-                AppView::PackageList => {
+            // Package details panel
+            egui::SidePanel::right("details")
+                .resizable(false)
+                .min_width(300.0)
+                .show(ctx, |ui| {
                     let app_ref = &*self;
-                    self.package_grid.render(ui, app_ref);
+                    let mut events = Vec::new();
+                    self.package_details.render(ui, app_ref, &mut events);
+                    for event in events {
+                        self.event_manager.push(event);
+                    }
+                });
+
+            // Main content area
+            egui::CentralPanel::default().show(ctx, |ui| {
+                match self.current_view {
+                    AppView::Welcome => self.render_welcome_screen(ui),
+                    AppView::PackageList => {
+                        let app_ref = &*self;
+                        let mut events = Vec::new();
+                        self.package_grid.render(ui, app_ref, &mut events);
+                        for event in events {
+                            self.event_manager.push(event);
+                        }
+                    }
                 }
-            }
-        });
+            });
+        }
 
         // Settings modal
         if self.show_settings {
             settings_modal::SettingsModal::render(ctx, self);
         }
+
+        // Persist any settings changed this frame
+        self.save_settings_if_changed();
+
+        // Add repository dialog
+        if self.add_repo_dialog.is_some() {
+            AddRepoDialog::render(ctx, self);
+        }
+
+        // Package cache manager
+        if self.cache_dialog.is_some() {
+            CacheDialog::render(ctx, self);
+        }
+
+        // Save search as smart collection
+        if self.save_collection_dialog.is_some() {
+            SaveCollectionDialog::render(ctx, self);
+        }
+
+        // pisi.conf diff preview
+        if self.pisi_conf_preview.is_some() {
+            PisiConfDialog::render(ctx, self);
+        }
+
+        // Database lock prompt
+        if self.lock_wait.is_some() {
+            LockDialog::render(ctx, self);
+        }
+
+        // Local package preview
+        if self.local_package.is_some() || self.local_package_message.is_some() {
+            LocalPackageDialog::render(ctx, self);
+        }
     }
 }
 
@@ -400,29 +1510,90 @@ impl PackageManagerApp {
             ui.horizontal(|ui| {
                 // Küçük logo - temaya göre değişen
                 self.render_small_logo(ui, 24.0);
-                ui.heading("Pisi Paket Yöneticisi");
+                ui.heading(t!("Pisi Package Manager"));
             });
             
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                // Theme toggle
-                let theme_text = if self.current_theme == Theme::Light {
-                    "🌙 Dark"
-                } else {
-                    "☀️ Light"
-                };
+                // Theme toggle: Light -> Dark -> System
+                let theme = self.settings.theme.clone();
+                let theme_text = format!("{} {}", theme.icon(), t!(theme.name()));
                 
-                if ui.button(theme_text).clicked() {
-                    self.current_theme = self.current_theme.next();
+                if ui.button(theme_text)
+                    .on_hover_text(t!("Switch to {} theme", t!(theme.next().name())))
+                    .clicked()
+                {
+                    self.settings.theme = theme.next();
+                    self.mark_settings_changed();
                 }
                 
                 // Settings button
-                if ui.button("⚙️ Ayarlar").clicked() {
+                if ui.button(t!("⚙️ Settings")).clicked() {
                     self.show_settings = true;
                 }
+                
+                // Local .pisi file
+                if ui.button(t!("📂 Open Package")).clicked() {
+                    if let Some(path) = file_dialog::pick_pisi_file() {
+                        self.event_manager.push(AppEvent::OpenLocalPackage(path));
+                    }
+                }
+                
+                // Search box, sonuçlar yazarken güncellenir
+                if !self.search_query.is_empty() && ui.small_button("✖").clicked() {
+                    self.search_query.clear();
+                    self.update_search();
+                }
+                let hint = match self.search_mode {
+                    SearchMode::Names => t!("🔍 Search packages..."),
+                    SearchMode::Provides => t!("🔍 libfoo.so.2, foo.pc, /usr/bin/foo..."),
+                };
+                let search = ui.add(
+                    egui::TextEdit::singleline(&mut self.search_query)
+                        .hint_text(hint)
+                        .desired_width(250.0),
+                );
+                if search.changed() {
+                    if self.search_mode == SearchMode::Provides && !self.search_query.trim().is_empty() {
+                        self.search_typed_at = Some(Instant::now());
+                    } else {
+                        self.update_search();
+                    }
+                }
+                
+                // Arama modu; sağdan sola dizildiği için kutunun solunda görünür
+                let mode = self.search_mode;
+                ui.selectable_value(&mut self.search_mode, SearchMode::Provides, t!("Provides"))
+                    .on_hover_text(t!("Find the package that provides a library, pkg-config name or file"));
+                ui.selectable_value(&mut self.search_mode, SearchMode::Names, t!("Names"))
+                    .on_hover_text(t!("Search package names and descriptions"));
+                if self.search_mode != mode {
+                    self.update_search();
+                }
             });
         });
     }
 
+    fn render_loading_screen(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(60.0);
+            self.render_main_logo(ui);
+            ui.add_space(30.0);
+            
+            ui.heading(t!("Loading package indexes..."));
+            ui.add_space(10.0);
+            
+            let (parsed, known) = self.load_progress.packages();
+            let (done, total) = self.load_progress.repositories();
+            ui.add(
+                egui::ProgressBar::new(self.load_progress.fraction())
+                    .desired_width(360.0)
+                    .text(t!("{} / {} packages", parsed, known)),
+            );
+            ui.add_space(6.0);
+            ui.label(t!("{} of {} repositories read", done, total));
+        });
+    }
+
     fn render_welcome_screen(&mut self, ui: &mut egui::Ui) {
         ui.vertical_centered(|ui| {
             ui.add_space(60.0);
@@ -433,9 +1604,9 @@ impl PackageManagerApp {
             ui.add_space(30.0);
             
             // Welcome message
-            ui.heading("Pisi GNU/Linux Paket Yöneticisi");
+            ui.heading(t!("Pisi GNU/Linux Package Manager"));
             ui.add_space(10.0);
-            ui.label("Sol taraftaki kategorilerden birini seçerek paketleri görüntüleyebilirsiniz.");
+            ui.label(t!("Select a category on the left to browse packages."));
             
             ui.add_space(40.0);
             
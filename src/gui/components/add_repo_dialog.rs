@@ -0,0 +1,209 @@
+use eframe::egui;
+use crate::{t, tn};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
+use crate::gui::app::PackageManagerApp;
+use crate::backend::repository::RepositoryManager;
+use crate::config::Repository;
+use crate::backend::downloader::{DownloadOptions, Downloader};
+use crate::backend::xml_parser::IndexSummary;
+
+/// "Add Repo" penceresinin durumu
+#[derive(Default)]
+pub struct AddRepoDialog {
+    pub name: String,
+    pub url: String,
+    /// Son başarılı deneme hangi URL için yapıldı
+    tested_url: Option<String>,
+    test_result: Option<Result<IndexSummary, String>>,
+    test_receiver: Option<Receiver<Result<IndexSummary, String>>>,
+    /// `pisi add-repo` index'i indirir ve yetki sorar; arka planda çalışır
+    add_receiver: Option<Receiver<Result<(), String>>>,
+    error: Option<String>,
+}
+
+impl AddRepoDialog {
+    pub fn render(ctx: &egui::Context, app: &mut PackageManagerApp) {
+        let Some(dialog) = &mut app.add_repo_dialog else {
+            return;
+        };
+        let mut open = true;
+        let mut add_clicked = false;
+
+        dialog.poll_test(ctx);
+        if dialog.poll_add(ctx) {
+            // pisi yeni depoyu sona, etkin olarak ekler; dışarıdan eklenmiş sayılmasın
+            let repo = Repository { enabled: true, name: dialog.name.trim().to_string(), url: dialog.url.trim().to_string() };
+            app.add_repo_dialog = None;
+            app.settings.repositories.push(repo);
+            app.mark_settings_changed();
+            app.sync_repositories();
+            app.reload_packages();
+            return;
+        }
+        let adding = dialog.add_receiver.is_some();
+
+        egui::Window::new(t!("Add Repository"))
+        .id(egui::Id::new("add_repository_window"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.add_enabled_ui(!adding, |ui| egui::Grid::new("add_repo_form").num_columns(2).show(ui, |ui| {
+                ui.label(t!("Name:"));
+                ui.text_edit_singleline(&mut dialog.name);
+                ui.end_row();
+
+                ui.label(t!("URL:"));
+                ui.add(egui::TextEdit::singleline(&mut dialog.url).desired_width(350.0));
+                ui.end_row();
+            }));
+            ui.label(t!("http://, https://, file:// or a local path to pisi-index.xml(.xz)"));
+
+            // URL değişirse eski deneme sonucu geçersiz
+            if dialog.tested_url.as_deref() != Some(dialog.url.trim()) {
+                dialog.test_result = None;
+            }
+
+            let validation = RepositoryManager::validate_repository(
+                dialog.name.trim(),
+                dialog.url.trim(),
+                &app.settings.repositories,
+            );
+
+            ui.separator();
+
+            if let Err(e) = &validation {
+                ui.label(format!("⚠ {}", e));
+            }
+
+            let testing = dialog.test_receiver.is_some();
+            match &dialog.test_result {
+                _ if testing => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(t!("Fetching index..."));
+                    });
+                }
+                Some(Ok(summary)) => {
+                    ui.label(tn!(
+                        "✓ {n} package, {} {}",
+                        "✓ {n} packages, {} {}",
+                        summary.package_count,
+                        summary.distribution,
+                        summary.release
+                    ));
+                }
+                Some(Err(e)) => {
+                    ui.label(t!("✗ Test failed: {}", e));
+                }
+                None => {}
+            }
+
+            if let Some(error) = &dialog.error {
+                ui.label(format!("✗ {}", error));
+            }
+
+            ui.horizontal(|ui| {
+                if ui.add_enabled(validation.is_ok() && !testing && !adding, egui::Button::new(t!("Test"))).clicked() {
+                    dialog.start_test(DownloadOptions::from_settings(&app.settings.proxy, &app.settings.cache));
+                }
+
+                let tested = matches!(dialog.test_result, Some(Ok(_)));
+                if ui.add_enabled(validation.is_ok() && tested && !adding, egui::Button::new(t!("Add"))).clicked() {
+                    add_clicked = true;
+                }
+                if adding {
+                    ui.spinner();
+                    ui.label(t!("Adding repository..."));
+                }
+            });
+        });
+
+        if add_clicked {
+            dialog.start_add();
+        }
+
+        // Ekleme sürerken pencere kapatılırsa sonuç beklenmez; Repos sekmesi
+        // açılınca pisi'nin listesi okunur
+        if !open {
+            app.add_repo_dialog = None;
+        }
+    }
+
+    /// Index'i arka planda indir, UI donmasın
+    fn start_test(&mut self, options: DownloadOptions) {
+        let url = self.url.trim().to_string();
+        let (sender, receiver) = mpsc::channel();
+
+        self.tested_url = Some(url.clone());
+        self.test_result = None;
+        self.error = None;
+        self.test_receiver = Some(receiver);
+
+        std::thread::spawn(move || {
+            let downloader = Downloader::new(options);
+            let result = RepositoryManager::test_repository(&url, &downloader).map_err(|e| e.to_string());
+            let _ = sender.send(result);
+        });
+    }
+
+    fn start_add(&mut self) {
+        let name = self.name.trim().to_string();
+        let url = self.url.trim().to_string();
+        let (sender, receiver) = mpsc::channel();
+
+        self.error = None;
+        self.add_receiver = Some(receiver);
+
+        std::thread::spawn(move || {
+            let result = RepositoryManager::add_repository(&name, &url).map_err(|e| e.to_string());
+            let _ = sender.send(result);
+        });
+    }
+
+    /// Ekleme bittiyse ve başarılıysa true
+    fn poll_add(&mut self, ctx: &egui::Context) -> bool {
+        let Some(receiver) = &self.add_receiver else {
+            return false;
+        };
+
+        match receiver.try_recv() {
+            Ok(result) => {
+                self.add_receiver = None;
+                match result {
+                    Ok(()) => return true,
+                    Err(e) => self.error = Some(e),
+                }
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.error = Some(t!("Adding the repository was interrupted"));
+                self.add_receiver = None;
+            }
+        }
+        false
+    }
+
+    fn poll_test(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.test_receiver else {
+            return;
+        };
+
+        match receiver.try_recv() {
+            Ok(result) => {
+                self.test_result = Some(result);
+                self.test_receiver = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => {
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.test_result = Some(Err(t!("Test was interrupted")));
+                self.test_receiver = None;
+            }
+        }
+    }
+}
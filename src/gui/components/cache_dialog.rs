@@ -0,0 +1,165 @@
+use eframe::egui;
+use crate::{t, tn};
+use std::path::Path;
+use crate::gui::app::PackageManagerApp;
+use crate::backend::cache::{format_size, CacheManager, CacheReport, CachedPackage, CleanupPolicy};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PolicyChoice {
+    KeepLast,
+    NotInstalled,
+    TrimToLimit,
+    ClearAll,
+}
+
+/// Önbellek yöneticisi penceresinin durumu
+pub struct CacheDialog {
+    report: Result<CacheReport, String>,
+    policy: PolicyChoice,
+    keep_versions: usize,
+    /// Önizlenen silme listesi
+    plan: Option<Vec<CachedPackage>>,
+    message: Option<String>,
+}
+
+impl CacheDialog {
+    pub fn new(directory: &str) -> Self {
+        Self {
+            report: CacheManager::scan(Path::new(directory)).map_err(|e| e.to_string()),
+            policy: PolicyChoice::KeepLast,
+            keep_versions: 1,
+            plan: None,
+            message: None,
+        }
+    }
+
+    fn cleanup_policy(&self, limit_mb: u32) -> CleanupPolicy {
+        match self.policy {
+            PolicyChoice::KeepLast => CleanupPolicy::KeepLastVersions(self.keep_versions),
+            PolicyChoice::NotInstalled => CleanupPolicy::RemoveNotInstalled,
+            PolicyChoice::TrimToLimit => CleanupPolicy::TrimToSize(limit_mb as u64 * 1024 * 1024),
+            PolicyChoice::ClearAll => CleanupPolicy::ClearAll,
+        }
+    }
+
+    pub fn render(ctx: &egui::Context, app: &mut PackageManagerApp) {
+        let Some(dialog) = &mut app.cache_dialog else {
+            return;
+        };
+        let mut open = true;
+        let mut delete_clicked = false;
+        let limit_mb = app.settings.cache.cache_size_mb;
+
+        egui::Window::new(t!("Package Cache"))
+        .id(egui::Id::new("package_cache_window"))
+        .open(&mut open)
+        .resizable(true)
+        .default_width(500.0)
+        .show(ctx, |ui| {
+            ui.label(t!("Directory: {}", app.settings.cache.cache_directory));
+
+            let report = match &dialog.report {
+                Ok(report) => report,
+                Err(e) => {
+                    ui.label(format!("✗ {}", e));
+                    return;
+                }
+            };
+
+            ui.label(t!(
+                "{} from {}, {} in total (limit {} MB)",
+                tn!("{n} file", "{n} files", report.file_count()),
+                tn!("{n} package", "{n} packages", report.packages.len()),
+                format_size(report.total_size),
+                limit_mb
+            ));
+
+            ui.collapsing(t!("Usage by package"), |ui| {
+                let mut by_size: Vec<(&String, u64, usize)> = report
+                    .packages
+                    .iter()
+                    .map(|(name, versions)| (name, versions.iter().map(|p| p.size).sum(), versions.len()))
+                    .collect();
+                by_size.sort_by_key(|(_, size, _)| std::cmp::Reverse(*size));
+
+                egui::ScrollArea::vertical().max_height(200.0).id_source("cache_usage").show(ui, |ui| {
+                    for (name, size, count) in by_size {
+                        ui.label(tn!("{}: {} ({n} version)", "{}: {} ({n} versions)", count, name, format_size(size)));
+                    }
+                });
+            });
+
+            ui.separator();
+            ui.heading(t!("Cleanup"));
+
+            let before = (dialog.policy, dialog.keep_versions);
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut dialog.policy, PolicyChoice::KeepLast, t!("Keep last"));
+                ui.add(egui::DragValue::new(&mut dialog.keep_versions).clamp_range(1..=10));
+                ui.label(t!("versions of each package"));
+            });
+            // Kurulu liste okunmadan her paket "kurulu değil" görünür ve tümü silinirdi
+            let states_loaded = app.installed_states_loaded;
+            ui.add_enabled_ui(states_loaded, |ui| {
+                ui.radio_value(&mut dialog.policy, PolicyChoice::NotInstalled, t!("Remove packages no longer installed"))
+            })
+            .inner
+            .on_disabled_hover_text(t!("Installed packages have not been read yet"));
+            if !states_loaded && dialog.policy == PolicyChoice::NotInstalled {
+                dialog.policy = PolicyChoice::KeepLast;
+            }
+            ui.radio_value(&mut dialog.policy, PolicyChoice::TrimToLimit, t!("Trim to size limit ({} MB)", limit_mb));
+            ui.radio_value(&mut dialog.policy, PolicyChoice::ClearAll, t!("Remove everything"));
+            if before != (dialog.policy, dialog.keep_versions) {
+                dialog.plan = None;
+            }
+
+            if ui.button(t!("Preview")).clicked() {
+                let policy = dialog.cleanup_policy(limit_mb);
+                dialog.plan = Some(CacheManager::plan(report, &policy, app.catalog.installed_names()));
+            }
+
+            if let Some(plan) = &dialog.plan {
+                let freed: u64 = plan.iter().map(|p| p.size).sum();
+                ui.label(tn!(
+                    "{n} file will be deleted, freeing {}",
+                    "{n} files will be deleted, freeing {}",
+                    plan.len(),
+                    format_size(freed)
+                ));
+
+                egui::ScrollArea::vertical().max_height(200.0).id_source("cache_plan").show(ui, |ui| {
+                    for package in plan {
+                        let file_name = package.path.file_name().unwrap_or_default().to_string_lossy();
+                        ui.label(format!("• {} ({})", file_name, format_size(package.size)));
+                    }
+                });
+
+                if ui.add_enabled(!plan.is_empty(), egui::Button::new(t!("🗑️ Delete"))).clicked() {
+                    delete_clicked = true;
+                }
+            }
+
+            if let Some(message) = &dialog.message {
+                ui.separator();
+                ui.label(message);
+            }
+        });
+
+        if delete_clicked {
+            if let Some(plan) = dialog.plan.take() {
+                let message = match CacheManager::delete(&plan) {
+                    Ok(freed) => t!("✓ Freed {}", format_size(freed)),
+                    Err(e) => format!("✗ {}", e),
+                };
+                // Silme sonrası raporu yenile
+                *dialog = Self::new(&app.settings.cache.cache_directory);
+                dialog.message = Some(message);
+            }
+        }
+
+        if !open {
+            app.cache_dialog = None;
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use eframe::egui;
+use std::collections::BTreeSet;
+use crate::t;
+use crate::gui::app::PackageManagerApp;
+use crate::gui::events::AppEvent;
+use crate::backend::filter::{FilterChip, InstallState, SizeRange, SortOrder, UpdatedWithin};
+use crate::backend::xml_parser::PackageInfo;
+
+/// Paket listesinin üstündeki filtre ve sıralama çubuğu
+#[derive(Default)]
+pub struct FilterBar;
+
+impl FilterBar {
+    pub fn render(&self, ui: &mut egui::Ui, app: &PackageManagerApp, events: &mut Vec<AppEvent>) {
+        let mut filter = app.package_filter.clone();
+
+        ui.horizontal_wrapped(|ui| {
+            egui::ComboBox::from_id_source("filter_state")
+                .selected_text(t!(filter.state.name()))
+                .show_ui(ui, |ui| {
+                    for state in InstallState::ALL {
+                        ui.selectable_value(&mut filter.state, state, t!(state.name()));
+                    }
+                });
+
+            Self::value_combo(ui, "filter_license", &t!("Any license"), &mut filter.license, app, |p| &*p.license);
+            Self::value_combo(ui, "filter_architecture", &t!("Any architecture"), &mut filter.architecture, app, |p| {
+                &*p.architecture
+            });
+            Self::value_combo(ui, "filter_repository", &t!("Any repository"), &mut filter.repository, app, |p| {
+                &*p.repository
+            });
+
+            ui.label(t!("Download:"));
+            Self::size_combo(ui, "filter_download_size", &mut filter.download_size);
+            ui.label(t!("Installed:"));
+            Self::size_combo(ui, "filter_installed_size", &mut filter.installed_size);
+
+            ui.label(t!("Updated:"));
+            egui::ComboBox::from_id_source("filter_updated")
+                .selected_text(t!(filter.updated_within.name()))
+                .show_ui(ui, |ui| {
+                    for within in UpdatedWithin::ALL {
+                        ui.selectable_value(&mut filter.updated_within, within, t!(within.name()));
+                    }
+                });
+
+            ui.separator();
+
+            ui.label(t!("Sort by:"));
+            egui::ComboBox::from_id_source("filter_sort")
+                .selected_text(t!(filter.sort.name()))
+                .show_ui(ui, |ui| {
+                    for sort in SortOrder::ALL {
+                        ui.selectable_value(&mut filter.sort, sort, t!(sort.name()));
+                    }
+                });
+            let (arrow, hint) = if filter.descending {
+                ("⬇", t!("Descending"))
+            } else {
+                ("⬆", t!("Ascending"))
+            };
+            let direction = ui.add_enabled(filter.sort != SortOrder::Default, egui::Button::new(arrow));
+            if direction.on_hover_text(hint).clicked() {
+                filter.descending = !filter.descending;
+            }
+
+            ui.separator();
+
+            if ui.button(t!("⭐ Save as Collection"))
+                .on_hover_text(t!("Keep this search and these filters in the sidebar"))
+                .clicked()
+            {
+                events.push(AppEvent::SaveCollection);
+            }
+        });
+
+        // Etkin filtreler
+        let chips = filter.chips();
+        if !chips.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for chip in &chips {
+                    if app.palette.chip(ui, &Self::chip_label(chip)).clicked() {
+                        filter.remove(chip);
+                    }
+                }
+                if ui.small_button(t!("Clear filters")).clicked() {
+                    filter.clear();
+                }
+            });
+        }
+
+        if filter != app.package_filter {
+            events.push(AppEvent::FilterChanged(filter));
+        }
+    }
+
+    /// Paketlerde geçen değerlerden biri ya da hepsi; liste yalnızca açılınca hesaplanır
+    fn value_combo(
+        ui: &mut egui::Ui,
+        id: &str,
+        any_text: &str,
+        selected: &mut Option<String>,
+        app: &PackageManagerApp,
+        field: impl Fn(&PackageInfo) -> &str,
+    ) {
+        egui::ComboBox::from_id_source(id)
+            .selected_text(selected.clone().unwrap_or_else(|| any_text.to_string()))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(selected, None, any_text);
+                let values: BTreeSet<&str> = app.catalog
+                    .packages()
+                    .iter()
+                    .map(&field)
+                    .filter(|value| !value.is_empty())
+                    .collect();
+                for value in values {
+                    ui.selectable_value(selected, Some(value.to_string()), value);
+                }
+            });
+    }
+
+    /// Hazır aralıklar ve elle girilen sınırlar
+    fn size_combo(ui: &mut egui::Ui, id: &str, selected: &mut SizeRange) {
+        egui::ComboBox::from_id_source(id)
+            .selected_text(Self::size_label(selected))
+            .show_ui(ui, |ui| {
+                for range in SizeRange::PRESETS {
+                    ui.selectable_value(selected, range, Self::size_label(&range));
+                }
+                ui.separator();
+                // 0 sınır yok demektir
+                let mut min = selected.min_mb.unwrap_or(0);
+                let mut max = selected.max_mb.unwrap_or(0);
+                ui.horizontal(|ui| {
+                    ui.label(t!("Min (MB):"));
+                    if ui.add(egui::DragValue::new(&mut min).clamp_range(0..=100_000)).changed() {
+                        selected.min_mb = (min > 0).then_some(min);
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("Max (MB):"));
+                    if ui.add(egui::DragValue::new(&mut max).clamp_range(0..=100_000)).changed() {
+                        selected.max_mb = (max > 0).then_some(max);
+                    }
+                });
+            });
+    }
+
+    fn size_label(range: &SizeRange) -> String {
+        match (range.min_mb, range.max_mb) {
+            (None, None) => t!("Any size"),
+            (None, Some(max)) => t!("Under {} MB", max),
+            (Some(min), None) => t!("Over {} MB", min),
+            (Some(min), Some(max)) => t!("{}-{} MB", min, max),
+        }
+    }
+
+    fn chip_label(chip: &FilterChip) -> String {
+        match chip {
+            FilterChip::State(state) => t!(state.name()),
+            FilterChip::License(license) => t!("License: {}", license),
+            FilterChip::Architecture(architecture) => t!("Architecture: {}", architecture),
+            FilterChip::Repository(repository) => t!("Repository: {}", repository),
+            FilterChip::DownloadSize(range) => t!("Download: {}", Self::size_label(range)),
+            FilterChip::InstalledSize(range) => t!("Installed: {}", Self::size_label(range)),
+            FilterChip::Updated(within) => t!("Updated: {}", t!(within.name())),
+        }
+    }
+}
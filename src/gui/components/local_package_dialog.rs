@@ -0,0 +1,106 @@
+use eframe::egui;
+use crate::t;
+use crate::gui::app::PackageManagerApp;
+use crate::backend::cache::format_size;
+use crate::backend::package_manager::PackageOperation;
+
+pub struct LocalPackageDialog;
+
+impl LocalPackageDialog {
+    pub fn render(ctx: &egui::Context, app: &mut PackageManagerApp) {
+        let mut open = true;
+        let mut install_clicked = false;
+
+        egui::Window::new(t!("Install Local Package"))
+        .id(egui::Id::new("install_local_package_window"))
+        .open(&mut open)
+        .resizable(true)
+        .default_width(500.0)
+        .show(ctx, |ui| {
+            if let Some(message) = &app.local_package_message {
+                ui.label(message);
+                ui.separator();
+            }
+
+            let Some(local) = &app.local_package else {
+                return;
+            };
+            let info = &local.info;
+
+            ui.heading(&info.name);
+            if !info.summary.is_empty() {
+                ui.label(&info.summary);
+            }
+            ui.label(t!("File: {}", local.path.display()));
+
+            ui.separator();
+
+            ui.label(t!("Version: {}-{}", info.version, info.release));
+            ui.label(t!("Architecture: {}", info.architecture));
+            if !info.license.is_empty() {
+                ui.label(t!("License: {}", info.license));
+            }
+            // Eski paketlerde InstalledSize yoksa dosya boyutlarının toplamı
+            let installed_size = match info.installed_size {
+                0 => local.total_files_size(),
+                size => size,
+            };
+            ui.label(t!("Installed Size: {}", format_size(installed_size)));
+
+            // Neyi değiştireceğini göster
+            ui.separator();
+            ui.label(t!("Replaces"));
+            if app.is_installed(&info.name) {
+                // Değişecek olan kurulu sürümdür, depodaki değil
+                let installed_version = app.catalog.installed_version(&info.name).unwrap_or("?");
+                ui.label(t!("• Installed {} {}", info.name, installed_version));
+            }
+            for replaced in &local.replaces {
+                let status = if app.is_installed(replaced) { t!("installed") } else { t!("not installed") };
+                ui.label(format!("• {} ({})", replaced, status));
+            }
+            if !app.is_installed(&info.name) && local.replaces.is_empty() {
+                ui.label(t!("Nothing, this is a new installation"));
+            }
+
+            ui.separator();
+
+            ui.collapsing(t!("Dependencies ({})", info.dependencies.len()), |ui| {
+                for dep in &info.dependencies {
+                    let mark = if app.is_installed(&dep.name) { "✓" } else { "✗" };
+                    let version = dep.version.as_deref()
+                        .map(|v| format!(" >= {}", v))
+                        .unwrap_or_default();
+                    ui.label(format!("{} {}{}", mark, dep.name, version));
+                }
+            });
+
+            ui.collapsing(t!("Files ({})", local.files.len()), |ui| {
+                egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                    for file in &local.files {
+                        ui.label(format!("/{}", file.path));
+                    }
+                });
+            });
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                if ui.button(t!("📥 Install")).clicked() {
+                    install_clicked = true;
+                }
+            });
+        });
+
+        if install_clicked {
+            if let Some(local) = app.local_package.take() {
+                app.start_operation(PackageOperation::InstallLocal(local.path));
+            }
+        }
+
+        if !open || install_clicked {
+            app.local_package = None;
+            app.local_package_message = None;
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use eframe::egui;
+use crate::t;
+use std::time::{Duration, Instant};
+use crate::gui::app::PackageManagerApp;
+use crate::backend::db_lock::LockHolder;
+use crate::backend::package_manager::PackageOperation;
+
+/// Kilit serbest kalana kadar en fazla bu kadar beklenir
+pub const LOCK_WAIT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Veritabanı kilidi yüzünden ertelenen işlem
+pub struct LockWait {
+    pub operation: PackageOperation,
+    pub holder: LockHolder,
+    /// Kullanıcı "Wait" dediyse bekleme başlangıcı
+    pub waiting_since: Option<Instant>,
+}
+
+impl LockWait {
+    pub fn new(operation: PackageOperation, holder: LockHolder) -> Self {
+        Self {
+            operation,
+            holder,
+            waiting_since: None,
+        }
+    }
+}
+
+pub struct LockDialog;
+
+impl LockDialog {
+    pub fn render(ctx: &egui::Context, app: &mut PackageManagerApp) {
+        let Some(lock_wait) = &mut app.lock_wait else {
+            return;
+        };
+        let mut abort = false;
+
+        egui::Window::new(t!("Package Database Locked"))
+        .id(egui::Id::new("package_database_locked_window"))
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(t!("Pending operation: {}", lock_wait.operation.description()));
+            ui.label(t!("The Pisi database is in use by:"));
+            ui.label(egui::RichText::new(lock_wait.holder.to_string()).strong());
+
+            ui.separator();
+
+            match lock_wait.waiting_since {
+                None => {
+                    ui.label(t!("Wait for it to finish, or abort the operation?"));
+                    ui.horizontal(|ui| {
+                        if ui.button(t!("⏳ Wait")).clicked() {
+                            lock_wait.waiting_since = Some(Instant::now());
+                        }
+                        if ui.button(t!("Abort")).clicked() {
+                            abort = true;
+                        }
+                    });
+                }
+                Some(since) => {
+                    let elapsed = since.elapsed().as_secs();
+                    ui.label(t!(
+                        "Waiting for the lock to be released... {}s / {}s",
+                        elapsed,
+                        LOCK_WAIT_TIMEOUT.as_secs()
+                    ));
+                    ui.add(egui::ProgressBar::new(
+                        since.elapsed().as_secs_f32() / LOCK_WAIT_TIMEOUT.as_secs_f32(),
+                    ));
+                    if ui.button(t!("Abort")).clicked() {
+                        abort = true;
+                    }
+                }
+            }
+        });
+
+        if abort {
+            let operation = lock_wait.operation.description();
+            app.lock_wait = None;
+            app.status_message = Some(t!("{} aborted: package database is locked", operation));
+        }
+    }
+}
@@ -1,9 +1,17 @@
 pub mod sidebar;
 pub mod package_grid;
+pub mod filter_bar;
 pub mod package_details;
 pub mod settings_modal;
+pub mod local_package_dialog;
+pub mod lock_dialog;
+pub mod add_repo_dialog;
+pub mod pisi_conf_dialog;
+pub mod cache_dialog;
+pub mod save_collection_dialog;
 
 pub use sidebar::Sidebar;
 pub use package_grid::PackageGrid;
+pub use filter_bar::FilterBar;
 pub use package_details::PackageDetails;
 // SettingsModal'ı doğrudan export etmiyoruz, çünkü struct zaten pub değil
@@ -1,57 +1,251 @@
 use eframe::egui;
+use crate::{t, tn};
 use crate::gui::app::PackageManagerApp;
-use crate::backend::package_manager::PackageManager;
+use crate::backend::cache::format_size;
+use crate::backend::package_manager::PackageOperation;
+use crate::backend::xml_parser::{Dependency, PackageHistory, PackageInfo};
+use crate::config::ThemePalette;
+use crate::gui::events::AppEvent;
 use crate::gui::app::AppView;  // AppView'ı import ediyoruz
 
 #[derive(Default)]
 pub struct PackageDetails;
 
 impl PackageDetails {
-    pub fn render(&self, ui: &mut egui::Ui, app: &PackageManagerApp) {
+    pub fn render(&self, ui: &mut egui::Ui, app: &PackageManagerApp, events: &mut Vec<AppEvent>) {
         ui.vertical(|ui| {
-            ui.heading("Package Details");
-
-            if app.current_view == AppView::Welcome {
-                ui.separator();
-                ui.label("Hoş geldiniz!");
-                ui.label("Sol taraftan bir kategori seçerek paketleri görüntüleyebilirsiniz.");
-                ui.add_space(10.0);
-                ui.label("Seçili kategori:");
-                ui.heading(&app.selected_category);
-            } else {
-                // Show package details when in package list view
-                ui.separator();
-                ui.label("Name: Firefox");
-                ui.label("Description: Web browser");
-                ui.label("Current Version: 115.0");
-                ui.label("Installed Version: 114.0");
-                ui.label("Repository: main");
-                ui.label("Size: 97 MB");
-
-                ui.separator();
-
-                // Action buttons
-                ui.horizontal(|ui| {
-                    if ui.button("Update Package").clicked() {
-                        if let Err(e) = PackageManager::update_package("firefox") {
-                            eprintln!("Update error: {}", e);
-                        }
+            ui.heading(t!("Package Details"));
+
+            egui::ScrollArea::vertical()
+                .id_source("package_details_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    if app.current_view == AppView::Welcome {
+                        ui.separator();
+                        ui.label(t!("Welcome!"));
+                        ui.label(t!("Select a category on the left to browse packages."));
+                        ui.add_space(10.0);
+                        ui.label(t!("Selected category:"));
+                        ui.heading(t!(app.selected_category.as_str()));
+                    } else if let Some(package) = app.selected_package() {
+                        ui.separator();
+                        self.render_package(ui, app, package, events);
+                    } else {
+                        ui.separator();
+                        ui.label(t!("Select a package to see its details."));
                     }
 
-                    if ui.button("Remove Package").clicked() {
-                        if let Err(e) = PackageManager::remove_package("firefox") {
-                            eprintln!("Remove error: {}", e);
-                        }
+                    ui.separator();
+
+                    // Info panel
+                    ui.label(t!("Information"));
+                    ui.label(t!("Total Packages: {}", app.catalog.len()));
+                    ui.label(egui::RichText::new(tn!("There is {n} package to update", "There are {n} packages to update", app.pending_updates().len()))
+                        .color(app.palette.update));
+                    if !app.settings.held_packages.is_empty() {
+                        ui.label(egui::RichText::new(tn!("🔒 {n} package held", "🔒 {n} packages held", app.settings.held_packages.len()))
+                            .color(app.palette.held));
                     }
                 });
+        });
+    }
+
+    /// Seçili paketin bilgileri ve işlemleri
+    fn render_package(&self, ui: &mut egui::Ui, app: &PackageManagerApp, package: &PackageInfo, events: &mut Vec<AppEvent>) {
+        let palette = &app.palette;
+        let installed = app.is_installed(&package.name);
+        let has_update = app.has_update(&package.name);
+        let held = app.settings.is_held(&package.name);
+
+        ui.horizontal_wrapped(|ui| {
+            ui.heading(&package.name);
+            if installed {
+                palette.badge(ui, &t!("Installed"), palette.installed);
+            }
+            if has_update {
+                palette.badge(ui, &t!("Update"), palette.update);
+            }
+            if held {
+                palette.badge(ui, &t!("🔒 Held"), palette.held)
+                    .on_hover_text(t!("This package is excluded from updates"));
             }
+        });
+        if !package.summary.is_empty() {
+            ui.label(egui::RichText::new(&package.summary).strong());
+        }
+        if !package.description.is_empty() && package.description != package.summary {
+            ui.add_space(4.0);
+            ui.label(egui::RichText::new(&package.description).color(palette.muted_text));
+        }
+
+        ui.separator();
+
+        // Sürümler: index'teki ile kurulu olan yan yana
+        let available = format!("{}-{}", package.version, package.release);
+        ui.label(t!("Available Version: {}", available));
+        match app.catalog.installed_version(&package.name) {
+            Some(version) => {
+                let text = t!("Installed Version: {}", version);
+                if has_update {
+                    ui.label(egui::RichText::new(text).color(palette.update));
+                } else {
+                    ui.label(text);
+                }
+            }
+            None if installed => {
+                ui.label(t!("Installed Version: {}", "?"));
+            }
+            None => {
+                ui.label(egui::RichText::new(t!("Not installed")).color(palette.muted_text));
+            }
+        }
+
+        ui.label(t!("Repository: {}", package.repository));
+        ui.label(t!("Category: {}", package.part_of));
+        ui.label(t!("Download Size: {}", format_size(package.package_size)));
+        ui.label(t!("Installed Size: {}", format_size(package.installed_size)));
+        let license = if package.license.is_empty() { "-" } else { &*package.license };
+        ui.label(t!("License: {}", license));
+        if !package.architecture.is_empty() {
+            ui.label(t!("Architecture: {}", package.architecture));
+        }
+        if let Some(homepage) = package.source.as_ref().map(|source| source.homepage.as_str()).filter(|url| !url.is_empty()) {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(t!("Homepage:"));
+                ui.hyperlink_to(homepage, homepage);
+            });
+        }
+
+        ui.separator();
+
+        // Action buttons
+        ui.horizontal_wrapped(|ui| {
+            if installed {
+                if has_update && ui.button(t!("🔄 Update")).clicked() {
+                    events.push(AppEvent::RunOperation(PackageOperation::Update(package.name.clone())));
+                }
 
-            ui.separator();
+                if ui.button(t!("🗑️ Remove")).clicked() {
+                    events.push(AppEvent::RunOperation(PackageOperation::Remove(package.name.clone())));
+                }
+            } else if ui.button(t!("📥 Install")).clicked() {
+                events.push(AppEvent::RunOperation(PackageOperation::Install(package.name.clone())));
+            }
 
-            // Info panel
-            ui.label("Bilgi");
-            ui.label(format!("Total Packages: {}", app.packages.len()));
-            ui.label("There are 15 packages to update");
+            let hold_text = if held { t!("🔓 Release") } else { t!("🔒 Hold") };
+            if ui.button(hold_text).clicked() {
+                events.push(AppEvent::TogglePackageHold(package.name.clone()));
+            }
         });
+
+        ui.separator();
+
+        egui::CollapsingHeader::new(t!("Dependencies ({})", package.dependencies.len()))
+            .id_source("package_details_dependencies")
+            .show(ui, |ui| {
+                if package.dependencies.is_empty() {
+                    ui.label(t!("No dependencies"));
+                }
+                for dependency in &package.dependencies {
+                    let text = Self::dependency_text(dependency);
+                    // Katalogdaki bağımlılığa tıklayınca onun detayları açılır
+                    match app.catalog.id(&dependency.name) {
+                        Some(id) => {
+                            let link = ui.link(text);
+                            let link = if app.is_installed(&dependency.name) {
+                                link.on_hover_text(t!("Installed"))
+                            } else {
+                                link
+                            };
+                            if link.clicked() {
+                                events.push(AppEvent::SelectPackage(id));
+                            }
+                        }
+                        None => {
+                            ui.label(egui::RichText::new(text).color(palette.muted_text));
+                        }
+                    }
+                }
+            });
+
+        // Değişiklik günlüğü; kurulu sürümden yeni kayıtlar güncellemenin getirdikleridir
+        let installed_version = app.catalog.installed_version(&package.name);
+        let is_new = |entry: &PackageHistory| installed_version.is_some_and(|version| entry.is_newer_than(version));
+        let new_entries = package.history.iter().filter(|entry| is_new(entry)).count();
+
+        egui::CollapsingHeader::new(t!("Changelog ({})", package.history.len()))
+            .id_source(("package_details_changelog", &package.name))
+            .default_open(new_entries > 0)
+            .show(ui, |ui| {
+                if package.history.is_empty() {
+                    ui.label(t!("No history in the index"));
+                }
+                if new_entries > 0 {
+                    ui.label(egui::RichText::new(tn!(
+                        "{n} change since your installed version",
+                        "{n} changes since your installed version",
+                        new_entries
+                    )).color(palette.update));
+                }
+                for entry in &package.history {
+                    self.render_history_entry(ui, palette, entry, is_new(entry));
+                    ui.add_space(4.0);
+                }
+            });
+    }
+
+    fn render_history_entry(&self, ui: &mut egui::Ui, palette: &ThemePalette, entry: &PackageHistory, is_new: bool) {
+        let stroke = if is_new {
+            egui::Stroke::new(1.5, palette.update)
+        } else {
+            egui::Stroke::new(1.0, palette.card_stroke)
+        };
+
+        egui::Frame::none()
+            .stroke(stroke)
+            .rounding(4.0)
+            .inner_margin(egui::Margin::same(6.0))
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(egui::RichText::new(format!("{}-{}", entry.version, entry.release)).strong());
+                    ui.label(egui::RichText::new(&entry.date).color(palette.muted_text));
+                    if is_new {
+                        palette.badge(ui, &t!("New"), palette.update);
+                    }
+                    if let Some(kind) = &entry.update_type {
+                        let (text, colour) = match kind.as_str() {
+                            "security" => (t!("Security"), palette.error),
+                            "critical" => (t!("Critical"), palette.error),
+                            other => (other.to_string(), palette.accent),
+                        };
+                        palette.badge(ui, &text, colour);
+                    }
+                });
+
+                if !entry.comment.is_empty() {
+                    ui.label(&entry.comment);
+                }
+
+                let packager = match (entry.name.is_empty(), entry.email.is_empty()) {
+                    (false, false) => format!("{} <{}>", entry.name, entry.email),
+                    (false, true) => entry.name.clone(),
+                    (true, false) => entry.email.clone(),
+                    (true, true) => String::new(),
+                };
+                if !packager.is_empty() {
+                    ui.label(egui::RichText::new(format!("— {}", packager)).small().color(palette.muted_text));
+                }
+            });
+    }
+
+    /// "glibc", "glibc (2.38)", "glibc (release 4)"
+    fn dependency_text(dependency: &Dependency) -> String {
+        match (&dependency.version, dependency.release) {
+            (Some(version), Some(release)) => format!("{} ({}-{})", dependency.name, version, release),
+            (Some(version), None) => format!("{} ({})", dependency.name, version),
+            (None, Some(release)) => t!("{} (release {})", dependency.name, release),
+            (None, None) => dependency.name.clone(),
+        }
     }
 }
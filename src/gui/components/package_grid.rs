@@ -1,127 +1,246 @@
 use eframe::egui;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use crate::backend::catalog::PackageId;
+use crate::{t, tn};
 use crate::gui::app::PackageManagerApp;
-use crate::backend::package_manager::PackageManager;
+use crate::backend::search::SearchMode;
 use crate::backend::xml_parser::PackageInfo;
+use crate::gui::events::AppEvent;
+use crate::gui::components::FilterBar;
+use crate::backend::package_manager::PackageOperation;
+use crate::backend::cache::format_size;
+
+/// Bir kartın en dar genişliği; sütun sayısı buna göre seçilir
+const MIN_CARD_WIDTH: f32 = 320.0;
+const MAX_COLUMNS: usize = 4;
+/// `ThemePalette::card_frame` ile aynı kenar boşluğu ve en kalın çerçeve
+const CARD_MARGIN: f32 = 8.0;
+const CARD_STROKE: f32 = 2.0;
 
 #[derive(Default)]
 pub struct PackageGrid;
 
+/// Liste değiştiğinde kaydırma konumunu korumak için egui belleğinde tutulur
+#[derive(Debug, Clone, Default)]
+struct ScrollAnchor {
+    list_key: u64,
+    /// Son karede en üst satırın ilk paketi
+    top_package: Option<String>,
+}
+
 impl PackageGrid {
-    pub fn render(&self, ui: &mut egui::Ui, app: &PackageManagerApp) {
+    pub fn render(&self, ui: &mut egui::Ui, app: &PackageManagerApp, events: &mut Vec<AppEvent>) {
+        let filtered_packages = &app.visible_packages;
+        
         ui.vertical(|ui| {
             // Category header
             ui.horizontal(|ui| {
-                ui.heading(&format!("{} Packages", app.selected_category));
+                if app.search_results.is_some() && app.search_mode == SearchMode::Provides {
+                    ui.heading(t!("Packages providing \"{}\"", app.search_query.trim()));
+                } else if app.search_results.is_some() {
+                    ui.heading(t!("Search results for \"{}\"", app.search_query.trim()));
+                } else {
+                    ui.heading(t!("{} Packages", t!(app.selected_category.as_str())));
+                }
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    ui.label(format!("{} packages found", self.get_filtered_packages(app).len()));
+                    if app.installed_files_loading.is_some() {
+                        ui.spinner();
+                        ui.label(t!("Reading installed file lists..."));
+                    }
+                    ui.label(tn!("{n} package found", "{n} packages found", filtered_packages.len()));
                 });
             });
             
+            // Filtreler ve sıralama
+            FilterBar.render(ui, app, events);
+            
             ui.separator();
             
-            // Package grid
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                let filtered_packages = self.get_filtered_packages(app);
-                
-                if filtered_packages.is_empty() {
-                    ui.vertical_centered(|ui| {
-                        ui.heading("No packages found");
-                        ui.label("Try selecting a different category or component");
-                    });
-                } else {
-                    // Use columns for package grid
-                    let columns = 3;
-                    let packages_per_column = (filtered_packages.len() + columns - 1) / columns;
-                    
-                    ui.columns(columns, |columns| {
-                        for (col_index, column) in columns.iter_mut().enumerate() {
-                            column.vertical(|ui| {
-                                let start_index = col_index * packages_per_column;
-                                let end_index = std::cmp::min(start_index + packages_per_column, filtered_packages.len());
-                                
-                                for i in start_index..end_index {
-                                    if let Some(package) = filtered_packages.get(i) {
-                                        self.render_package_card(ui, package);
-                                    }
-                                }
-                            });
-                        }
-                    });
-                }
-            });
+            if filtered_packages.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.heading(t!("No packages found"));
+                    if app.search_results.is_some() {
+                        ui.label(t!("Try a different search term"));
+                    } else {
+                        ui.label(t!("Try selecting a different category or component"));
+                    }
+                });
+            } else {
+                self.render_rows(ui, app, filtered_packages, events);
+            }
         });
     }
     
-    fn get_filtered_packages<'a>(&self, app: &'a PackageManagerApp) -> Vec<&'a PackageInfo> {
-        app.packages.iter()
-            .filter(|pkg| {
-                // Component filtreleme
-                let component_match = if app.selected_component == "All" {
-                    true
-                } else {
-                    pkg.part_of == app.selected_component
-                };
-                
-                component_match
-            })
-            .collect()
+    /// Yalnızca görünen satırları çiz; binlerce paket için her karede tüm kartları kurmamak için
+    fn render_rows(
+        &self,
+        ui: &mut egui::Ui,
+        app: &PackageManagerApp,
+        packages: &[PackageId],
+        events: &mut Vec<AppEvent>,
+    ) {
+        let spacing = ui.spacing().item_spacing;
+        let columns = ((ui.available_width() / MIN_CARD_WIDTH) as usize).clamp(1, MAX_COLUMNS);
+        let card_width = (ui.available_width() - spacing.x * (columns - 1) as f32) / columns as f32;
+        let card_height = self.card_height(ui, app);
+        let rows = packages.len().div_ceil(columns);
+        
+        // Liste değişince en üstte görünen paket yerinde kalsın
+        let state_id = ui.id().with("package_grid_scroll");
+        let mut state: ScrollAnchor = ui.data(|data| data.get_temp(state_id)).unwrap_or_default();
+        let list_key = Self::list_key(app.visible_packages_key, columns);
+        let mut scroll_area = egui::ScrollArea::vertical()
+            .id_source("package_grid_rows")
+            .auto_shrink([false, false]);
+        if state.list_key != list_key {
+            if let Some(row) = state
+                .top_package
+                .as_ref()
+                .and_then(|top| app.catalog.id(top))
+                .and_then(|top| packages.iter().position(|&id| id == top))
+                .map(|index| index / columns)
+            {
+                scroll_area = scroll_area.vertical_scroll_offset(row as f32 * (card_height + spacing.y));
+            }
+        }
+        
+        let output = scroll_area.show_rows(ui, card_height, rows, |ui, row_range| {
+            for row in row_range.clone() {
+                ui.horizontal(|ui| {
+                    let row_packages = packages.iter().skip(row * columns).take(columns);
+                    for package in row_packages.filter_map(|&id| app.catalog.get(id)) {
+                        let size = egui::vec2(card_width, card_height);
+                        ui.allocate_ui_with_layout(size, egui::Layout::top_down(egui::Align::Min), |ui| {
+                            // Kart içeriği sabit yüksekliği aşarsa komşu satıra taşmasın
+                            let clip = ui.max_rect().intersect(ui.clip_rect());
+                            ui.set_clip_rect(clip);
+                            self.render_package_card(ui, app, package, card_height, events);
+                        });
+                    }
+                });
+            }
+            row_range.start
+        });
+        
+        state.list_key = list_key;
+        state.top_package = packages
+            .get(output.inner * columns)
+            .and_then(|&id| app.catalog.get(id))
+            .map(|package| package.name.clone());
+        ui.data_mut(|data| data.insert_temp(state_id, state));
     }
     
-    fn render_package_card(&self, ui: &mut egui::Ui, package: &PackageInfo) {
-        egui::Frame::group(ui.style()).show(ui, |ui| {
-            ui.vertical(|ui| {
-                // Package header
-                ui.horizontal(|ui| {
-                    // Paket ikonu
-                    let icon = self.get_package_icon(&package.name);
-                    ui.label(icon);
+    /// Kartlar sabit yüksekliktedir, satır sanallaştırması buna dayanır
+    fn card_height(&self, ui: &egui::Ui, app: &PackageManagerApp) -> f32 {
+        let spacing = ui.spacing();
+        let body = ui.text_style_height(&egui::TextStyle::Body);
+        let heading = ui.text_style_height(&egui::TextStyle::Heading).max(spacing.interact_size.y);
+        let button = spacing.interact_size.y.max(body + 2.0 * spacing.button_padding.y);
+        
+        // Özet, sürüm, kategori, boyut, lisans (+ Provides eşleşmesi)
+        let text_lines = if app.provider_matches.is_empty() { 5 } else { 6 };
+        let items = text_lines + 2;
+        let content = heading + body * text_lines as f32 + button + spacing.item_spacing.y * (items - 1) as f32;
+        content + 2.0 * (CARD_MARGIN + CARD_STROKE)
+    }
+    
+    /// Gösterilen listenin ve sütun sayısının kimliği; değişince kaydırma çapası uygulanır
+    fn list_key(packages_key: u64, columns: usize) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        packages_key.hash(&mut hasher);
+        columns.hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    fn render_package_card(
+        &self,
+        ui: &mut egui::Ui,
+        app: &PackageManagerApp,
+        package: &PackageInfo,
+        card_height: f32,
+        events: &mut Vec<AppEvent>,
+    ) {
+        let held = app.settings.is_held(&package.name);
+        let installed = app.is_installed(&package.name);
+        let palette = &app.palette;
+        let inner_height = card_height - 2.0 * (CARD_MARGIN + CARD_STROKE);
+        let inner_width = ui.available_width() - 2.0 * (CARD_MARGIN + CARD_STROKE);
+        let selected = app.selected_package().is_some_and(|selected| selected.name == package.name);
+        
+        let card = palette.card_frame(selected).show(ui, |ui| {
+            ui.set_min_size(egui::vec2(inner_width, inner_height));
+            ui.set_max_width(inner_width);
+            
+            // Package header
+            ui.horizontal(|ui| {
+                // Paket ikonu
+                let icon = self.get_package_icon(&package.name);
+                ui.label(icon);
+                ui.heading(&package.name);
+                if installed {
+                    palette.badge(ui, &t!("Installed"), palette.installed);
+                }
+                if app.has_update(&package.name) {
+                    palette.badge(ui, &t!("Update"), palette.update);
+                }
+                if held {
+                    palette.badge(ui, &t!("🔒 Held"), palette.held)
+                        .on_hover_text(t!("This package is excluded from updates"));
+                }
+            });
+            
+            // Satır sayısı sabit kalsın diye boş alanlar da tek satır yer kaplar
+            let summary = if package.summary.is_empty() { " " } else { package.summary.as_str() };
+            ui.add(egui::Label::new(egui::RichText::new(summary).color(palette.muted_text)).truncate(true))
+                .on_hover_text(&package.summary);
+            if !app.provider_matches.is_empty() {
+                let provided = app.provider_matches.get(&package.name).map(String::as_str).unwrap_or_default();
+                ui.add(egui::Label::new(egui::RichText::new(t!("Provides: {}", provided)).color(palette.accent)).truncate(true));
+            }
+            
+            // Version info
+            ui.add(egui::Label::new(t!("Version: {}", package.version)).truncate(true));
+            
+            // Component info
+            let formatted_component = Self::format_component_name(&package.part_of);
+            ui.add(egui::Label::new(t!("Category: {}", formatted_component)).truncate(true));
+            
+            // Size info
+            ui.label(t!("Download Size: {}", format_size(package.package_size)));
+            
+            // License
+            let license = if package.license.is_empty() { "-" } else { &*package.license };
+            ui.add(egui::Label::new(t!("License: {}", license)).truncate(true));
+            
+            // Action buttons
+            ui.horizontal(|ui| {
+                if installed {
+                    if app.has_update(&package.name) && ui.button(t!("🔄 Update")).clicked() {
+                        events.push(AppEvent::RunOperation(PackageOperation::Update(package.name.clone())));
+                    }
                     
-                    ui.vertical(|ui| {
-                        ui.heading(&package.name);
-                        if !package.summary.is_empty() {
-                            ui.label(&package.summary);
-                        }
-                    });
-                });
-                
-                // Version info
-                ui.label(format!("Version: {}", package.version));
-                
-                // Component info
-                let formatted_component = Self::format_component_name(&package.part_of);
-                ui.label(format!("Category: {}", formatted_component));
-                
-                // Size info - MB cinsinden göster
-                let size_mb = package.package_size / 1_000_000;
-                if size_mb > 0 {
-                    ui.label(format!("Download Size: {} MB", size_mb));
+                    if ui.button(t!("🗑️ Remove")).clicked() {
+                        events.push(AppEvent::RunOperation(PackageOperation::Remove(package.name.clone())));
+                    }
+                } else if ui.button(t!("📥 Install")).clicked() {
+                    events.push(AppEvent::RunOperation(PackageOperation::Install(package.name.clone())));
                 }
                 
-                // License
-                if !package.license.is_empty() {
-                    ui.label(format!("License: {}", package.license));
+                let hold_text = if held { t!("🔓 Release") } else { t!("🔒 Hold") };
+                if ui.button(hold_text).clicked() {
+                    events.push(AppEvent::TogglePackageHold(package.name.clone()));
                 }
-                
-                // Action buttons
-                ui.horizontal(|ui| {
-                    if self.is_package_installed(&package.name) {
-                        if self.has_package_update(&package.name) {
-                            if ui.button("🔄 Update").clicked() {
-                                // Update işlemi
-                            }
-                        }
-                        
-                        if ui.button("🗑️ Remove").clicked() {
-                            // Remove işlemi
-                        }
-                    } else {
-                        if ui.button("📥 Install").clicked() {
-                            // Install işlemi
-                        }
-                    }
-                });
             });
         });
+        
+        // Kartın herhangi bir yerine tıklamak paketi detay panelinde açar;
+        // butonlar da tıklandıkları paketi seçer
+        if card.response.hovered() && ui.input(|i| i.pointer.primary_clicked()) {
+            if let Some(id) = app.catalog.id(&package.name) {
+                events.push(AppEvent::SelectPackage(id));
+            }
+        }
     }
     
     fn format_component_name(raw_name: &str) -> String {
@@ -139,17 +258,6 @@ impl PackageGrid {
             .join(" ")
     }
     
-    // Geçici implementasyonlar - bunları daha sonra gerçek Pisi komutlarıyla değiştireceğiz
-    fn is_package_installed(&self, _package_name: &str) -> bool {
-        // Geçici: Her zaman false döndür
-        false
-    }
-    
-    fn has_package_update(&self, _package_name: &str) -> bool {
-        // Geçici: Her zaman false döndür  
-        false
-    }
-    
     fn get_package_icon(&self, package_name: &str) -> &'static str {
         // Basit paket ikonu mapping
         match package_name {
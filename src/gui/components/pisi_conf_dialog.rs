@@ -0,0 +1,111 @@
+use eframe::egui;
+use crate::t;
+use crate::gui::app::PackageManagerApp;
+use crate::backend::pisi_conf::{diff_lines, mask_password, DiffLine, PisiConf};
+
+/// pisi.conf'a yazılacak değişikliklerin önizlemesi
+pub struct PisiConfPreview {
+    pub conf: PisiConf,
+    pub diff: Vec<DiffLine>,
+    pub error: Option<String>,
+}
+
+impl PisiConfPreview {
+    /// Mevcut pisi.conf'u oku, uygulama ayarlarını üzerine uygula ve dosyanın
+    /// gerçek metniyle karşılaştır; proxy parolaları gizlenir
+    pub fn from_app(app: &PackageManagerApp) -> Result<Self, String> {
+        let mut conf = PisiConf::load().map_err(|e| e.to_string())?;
+        conf.apply_settings(&app.settings.proxy, &app.settings.cache);
+
+        // Karşılaştırma gerçek metinle yapılır; yalnızca parola değişse de fark görünür
+        let diff = diff_lines(conf.original_content(), &conf.to_content())
+            .into_iter()
+            .map(|line| match line {
+                DiffLine::Same(text) => DiffLine::Same(mask_password(&text)),
+                DiffLine::Added(text) => DiffLine::Added(mask_password(&text)),
+                DiffLine::Removed(text) => DiffLine::Removed(mask_password(&text)),
+            })
+            .collect();
+
+        Ok(Self {
+            diff,
+            conf,
+            error: None,
+        })
+    }
+}
+
+pub struct PisiConfDialog;
+
+impl PisiConfDialog {
+    pub fn render(ctx: &egui::Context, app: &mut PackageManagerApp) {
+        let Some(preview) = &mut app.pisi_conf_preview else {
+            return;
+        };
+        let mut open = true;
+        let mut apply_clicked = false;
+        let palette = app.palette;
+
+        egui::Window::new(t!("Apply to pisi.conf"))
+        .id(egui::Id::new("apply_to_pisi_conf_window"))
+        .open(&mut open)
+        .resizable(true)
+        .default_width(600.0)
+        .show(ctx, |ui| {
+            ui.label(t!("Changes to {}:", preview.conf.path().display()));
+            ui.separator();
+
+            let changed = preview.diff.iter().any(|line| !matches!(line, DiffLine::Same(_)));
+            if !changed {
+                ui.label(t!("pisi.conf already matches these settings."));
+            }
+
+            egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                for line in &preview.diff {
+                    match line {
+                        // Değişmeyen satırlardan sadece bölüm başlıklarını göster
+                        DiffLine::Same(text) if text.starts_with('[') => {
+                            ui.monospace(text);
+                        }
+                        DiffLine::Same(_) => {}
+                        DiffLine::Added(text) => {
+                            ui.label(egui::RichText::new(format!("+ {}", text))
+                                .monospace()
+                                .color(palette.success));
+                        }
+                        DiffLine::Removed(text) => {
+                            ui.label(egui::RichText::new(format!("- {}", text))
+                                .monospace()
+                                .color(palette.error));
+                        }
+                    }
+                }
+            });
+
+            if let Some(error) = &preview.error {
+                ui.separator();
+                ui.label(format!("✗ {}", error));
+            }
+
+            ui.separator();
+            if ui.add_enabled(changed, egui::Button::new(t!("Apply"))).clicked() {
+                apply_clicked = true;
+            }
+        });
+
+        if apply_clicked {
+            match preview.conf.save() {
+                Ok(()) => {
+                    app.pisi_conf_preview = None;
+                    app.status_message = Some(t!("pisi.conf updated"));
+                    return;
+                }
+                Err(e) => preview.error = Some(e.to_string()),
+            }
+        }
+
+        if !open {
+            app.pisi_conf_preview = None;
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use eframe::egui;
+use crate::t;
+use crate::gui::app::PackageManagerApp;
+use crate::backend::search::SearchMode;
+
+/// Ekrandaki arama ve filtreleri akıllı koleksiyon olarak kaydetme penceresi
+#[derive(Default)]
+pub struct SaveCollectionDialog {
+    pub name: String,
+}
+
+impl SaveCollectionDialog {
+    pub fn render(ctx: &egui::Context, app: &mut PackageManagerApp) {
+        let Some(dialog) = &mut app.save_collection_dialog else {
+            return;
+        };
+        let mut open = true;
+        let mut save_clicked = false;
+
+        egui::Window::new(t!("Save Smart Collection"))
+        .id(egui::Id::new("save_collection_window"))
+        .open(&mut open)
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("Name:"));
+                let response = ui.text_edit_singleline(&mut dialog.name);
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    save_clicked = true;
+                }
+            });
+
+            // Kaydedilecek olanın özeti
+            let query = app.search_query.trim();
+            if query.is_empty() {
+                ui.label(t!("Search: all packages"));
+            } else if app.search_mode == SearchMode::Provides {
+                ui.label(t!("Provides: {}", query));
+            } else {
+                ui.label(t!("Search: {}", query));
+            }
+            let chips = app.package_filter.chips();
+            ui.label(t!("Active filters: {}", chips.len()));
+
+            let name = dialog.name.trim();
+            if app.settings.smart_collections.iter().any(|collection| collection.name == name) {
+                ui.label(t!("⚠ A collection named \"{}\" will be replaced", name));
+            }
+
+            ui.separator();
+            if ui.add_enabled(!name.is_empty(), egui::Button::new(t!("Save"))).clicked() {
+                save_clicked = true;
+            }
+        });
+
+        if save_clicked && !dialog.name.trim().is_empty() {
+            let name = dialog.name.clone();
+            app.save_collection(&name);
+            app.save_collection_dialog = None;
+            return;
+        }
+
+        if !open {
+            app.save_collection_dialog = None;
+        }
+    }
+}
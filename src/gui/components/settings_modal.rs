@@ -1,6 +1,10 @@
 use eframe::egui;
+use crate::{i18n, t};
 use crate::gui::app::PackageManagerApp;
-use crate::config::{Repository, SettingsTab};
+use crate::config::{paths, SettingsTab, Theme};
+use crate::gui::components::add_repo_dialog::AddRepoDialog;
+use crate::gui::components::cache_dialog::CacheDialog;
+use crate::backend::repository::RepoOperation;
 
 #[derive(Default)]
 pub struct SettingsModal;
@@ -9,7 +13,8 @@ impl SettingsModal {
     pub fn render(ctx: &egui::Context, app: &mut PackageManagerApp) {
         let mut show_settings = app.show_settings;
 
-        egui::Window::new("Package Manager Settings")
+        egui::Window::new(t!("Package Manager Settings"))
+        .id(egui::Id::new("package_manager_settings_window"))
         .open(&mut show_settings)
         .resizable(true)
         .default_width(600.0)
@@ -19,164 +24,370 @@ impl SettingsModal {
             ui.horizontal(|ui| {
                 // Left menu - sadece 4 ana sekme
                 ui.vertical(|ui| {
-                    ui.selectable_value(&mut current_tab, SettingsTab::General, "General");
-                    ui.selectable_value(&mut current_tab, SettingsTab::Cache, "Package Cache");
-                    ui.selectable_value(&mut current_tab, SettingsTab::Repos, "Package Repos");
-                    ui.selectable_value(&mut current_tab, SettingsTab::Proxy, "Proxy Settings");
+                    ui.selectable_value(&mut current_tab, SettingsTab::General, t!("General"));
+                    ui.selectable_value(&mut current_tab, SettingsTab::Cache, t!("Package Cache"));
+                    ui.selectable_value(&mut current_tab, SettingsTab::Repos, t!("Package Repos"));
+                    ui.selectable_value(&mut current_tab, SettingsTab::Proxy, t!("Proxy Settings"));
                 });
 
                 ui.separator();
 
                 // Right content
                 ui.vertical(|ui| {
-                    match current_tab {
+                    let changed = match current_tab {
                         SettingsTab::General => Self::render_general_settings(ui, app),
                             SettingsTab::Cache => Self::render_cache_settings(ui, app),
                             SettingsTab::Repos => Self::render_repo_settings(ui, app),
                             SettingsTab::Proxy => Self::render_proxy_settings(ui, app),
+                    };
+                    if changed {
+                        app.mark_settings_changed();
                     }
                 });
             });
 
+            // Repos sekmesine geçildiğinde ya da pencere bu sekmede açıldığında
+            // listeyi pisi ile eşitle
+            let entered_repos = app.settings_modal.current_tab != SettingsTab::Repos || !app.settings_modal.visible;
+            if current_tab == SettingsTab::Repos && entered_repos {
+                app.sync_repositories();
+            }
+
             // Update the tab in the app state
             app.settings_modal.current_tab = current_tab;
         });
 
         // Update show_settings flag
         app.show_settings = show_settings;
+        app.settings_modal.visible = show_settings;
     }
 
-    fn render_general_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) {
-        ui.heading("General Settings");
+    /// Ayarlardan biri değiştiyse true döner
+    fn render_general_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) -> bool {
+        let mut changed = false;
+        ui.heading(t!("General Settings"));
+
+        // Language
+        ui.horizontal(|ui| {
+            ui.label(t!("Language:"));
+            let selected = i18n::LANGUAGES
+                .iter()
+                .find(|(code, _, _)| *code == app.settings.language)
+                .map(|(_, name, _)| name.to_string())
+                .unwrap_or_else(|| t!("Automatic ({})", i18n::detect_language()));
+            let before = app.settings.language.clone();
+            egui::ComboBox::from_id_source("language_select")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut app.settings.language, String::new(), t!("Automatic ({})", i18n::detect_language()));
+                for (code, name, _) in i18n::LANGUAGES {
+                    ui.selectable_value(&mut app.settings.language, code.to_string(), name);
+                }
+            });
+            if app.settings.language != before {
+                i18n::set_language(&app.settings.language);
+                changed = true;
+            }
+        });
+
+        // Theme
+        ui.horizontal(|ui| {
+            ui.label(t!("Theme:"));
+            let user_themes: Vec<Theme> = app
+                .user_themes
+                .iter()
+                .map(|theme| Theme::Custom(theme.name.clone()))
+                .collect();
+            let before = app.settings.theme.clone();
+            egui::ComboBox::from_id_source("theme_select")
+            .selected_text(format!("{} {}", app.settings.theme.icon(), t!(app.settings.theme.name())))
+            .show_ui(ui, |ui| {
+                for theme in Theme::BUILT_IN.into_iter().chain(user_themes) {
+                    let label = format!("{} {}", theme.icon(), t!(theme.name()));
+                    ui.selectable_value(&mut app.settings.theme, theme, label);
+                }
+            });
+            changed |= app.settings.theme != before;
+            if app.settings.theme == Theme::System {
+                let detected = if app.system_prefers_dark { t!("dark") } else { t!("light") };
+                ui.label(t!("(desktop prefers {})", detected));
+            }
+            if ui.button(t!("🔄 Reload Themes")).clicked() {
+                app.reload_user_themes();
+            }
+        });
+        ui.label(t!("Theme files: {}", paths::themes_dir().display()));
+        if let Theme::Custom(name) = &app.settings.theme {
+            if !app.user_themes.iter().any(|theme| &theme.name == name) {
+                ui.label(t!("⚠ Theme \"{}\" was not found, using the desktop theme", name));
+            }
+        }
+        for error in &app.theme_errors {
+            ui.label(format!("⚠ {}", error));
+        }
 
         // System Tray
-        ui.checkbox(&mut app.settings.system_tray_enabled, "Enable system tray icon");
+        changed |= ui.checkbox(&mut app.settings.system_tray_enabled, t!("Enable system tray icon")).changed();
 
         ui.separator();
 
         // Package List View Settings
-        ui.heading("Package List View");
-        ui.checkbox(&mut app.settings.package_list.show_only_desktop_apps, "Show only desktop applications");
-        ui.checkbox(&mut app.settings.package_list.show_components_info, "Show components info");
-        ui.checkbox(&mut app.settings.package_list.show_package_type_labels, "Show labels of package type");
+        ui.heading(t!("Package List View"));
+        changed |= ui.checkbox(&mut app.settings.package_list.show_only_desktop_apps, t!("Show only desktop applications")).changed();
+        changed |= ui.checkbox(&mut app.settings.package_list.show_components_info, t!("Show components info")).changed();
+        changed |= ui.checkbox(&mut app.settings.package_list.show_package_type_labels, t!("Show labels of package type")).changed();
 
         ui.separator();
 
         // Update Settings
-        ui.heading("Update Settings");
-        ui.checkbox(&mut app.settings.update.check_updates, "Check updates");
+        ui.heading(t!("Update Settings"));
+        changed |= ui.checkbox(&mut app.settings.update.check_updates, t!("Check updates")).changed();
 
         ui.horizontal(|ui| {
             let mut enable_interval = app.settings.update.update_check_interval > 0;
-            ui.checkbox(&mut enable_interval, "Enable update check interval");
+            changed |= ui.checkbox(&mut enable_interval, t!("Enable update check interval")).changed();
             if enable_interval {
-                ui.add(egui::DragValue::new(&mut app.settings.update.update_check_interval).suffix(" m"));
+                changed |= ui.add(egui::DragValue::new(&mut app.settings.update.update_check_interval).suffix(" m")).changed();
             } else {
                 app.settings.update.update_check_interval = 0;
             }
         });
 
-        ui.checkbox(&mut app.settings.update.install_updates_automatically, "Install updates automatically");
+        changed |= ui.checkbox(&mut app.settings.update.install_updates_automatically, t!("Install updates automatically")).changed();
+
+        ui.separator();
+
+        // Held packages
+        ui.heading(t!("Held Packages"));
+        if app.settings.held_packages.is_empty() {
+            ui.label(t!("No packages are held back from updates."));
+        }
+        ui.label(egui::RichText::new(t!("A held package is still upgraded when another update requires its newer version."))
+            .small()
+            .color(app.palette.muted_text));
+
+        let mut released = None;
+        for package_name in &app.settings.held_packages {
+            ui.horizontal(|ui| {
+                ui.label(format!("🔒 {}", package_name));
+                if ui.small_button(t!("Release")).clicked() {
+                    released = Some(package_name.clone());
+                }
+            });
+        }
+        if let Some(package_name) = released {
+            app.settings.toggle_hold(&package_name);
+            app.invalidate_visible_packages();
+            changed = true;
+        }
+        changed
     }
 
-    fn render_cache_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) {
-        ui.heading("Package Cache");
-        ui.checkbox(&mut app.settings.cache.use_disk_cache, "Use disk cache for downloaded packages");
+    fn render_cache_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) -> bool {
+        let mut changed = false;
+        ui.heading(t!("Package Cache"));
+        changed |= ui.checkbox(&mut app.settings.cache.use_disk_cache, t!("Use disk cache for downloaded packages")).changed();
 
         ui.horizontal(|ui| {
-            ui.label("Cache size (MB):");
-            ui.add(egui::DragValue::new(&mut app.settings.cache.cache_size_mb));
+            ui.label(t!("Cache size (MB):"));
+            changed |= ui.add(egui::DragValue::new(&mut app.settings.cache.cache_size_mb)).changed();
         });
 
         ui.horizontal(|ui| {
-            ui.label("Cache directory:");
-            ui.text_edit_singleline(&mut app.settings.cache.cache_directory);
+            ui.label(t!("Cache directory:"));
+            changed |= ui.text_edit_singleline(&mut app.settings.cache.cache_directory).changed();
         });
 
-        if ui.button("Clear Cache").clicked() {
-            // Clear cache implementation
+        changed |= ui.add_enabled(
+            app.settings.cache.use_disk_cache && app.settings.cache.cache_size_mb > 0,
+            egui::Checkbox::new(
+                &mut app.settings.cache.trim_after_operations,
+                t!("Trim the cache to the size limit after package operations"),
+            ),
+        ).changed();
+
+        if ui.button(t!("Clear Cache...")).clicked() {
+            app.cache_dialog = Some(CacheDialog::new(&app.settings.cache.cache_directory));
         }
 
         ui.separator();
-        ui.checkbox(&mut app.settings.cache.use_disk_cache, "Use bandwidth limit");
-    }
-
-    fn render_repo_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) {
-        ui.heading("Package Repositories");
-
-        // Repository table
         ui.horizontal(|ui| {
-            ui.label("Enable");
-            ui.label("Name");
-            ui.label("URL");
+            changed |= ui.checkbox(&mut app.settings.cache.use_bandwidth_limit, t!("Use bandwidth limit")).changed();
+            changed |= ui.add_enabled(
+                app.settings.cache.use_bandwidth_limit,
+                egui::DragValue::new(&mut app.settings.cache.bandwidth_limit_kbps)
+                    .clamp_range(1..=1_000_000)
+                    .suffix(" KB/s"),
+            ).changed();
         });
+        ui.label(t!("Applies to pisi package downloads and the app's own index downloads."));
 
         ui.separator();
+        if ui.button(t!("Apply to pisi.conf...")).clicked() {
+            app.preview_pisi_conf();
+        }
+        changed
+    }
+
+    /// Depo değişiklikleri işlem bitince `on_repo_operation_finished` ve
+    /// `sync_repositories` üzerinden işaretlenir
+    fn render_repo_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) -> bool {
+        ui.heading(t!("Package Repositories"));
+        ui.label(t!("Repositories higher in the list take priority when a package exists in several."));
 
-        for repo in &mut app.settings.repositories {
+        // Depo işlemi sürerken liste ve düğmeler kilitli
+        let busy = app.running_repo_operation.is_some();
+        if let Some(operation) = &app.running_repo_operation {
             ui.horizontal(|ui| {
-                ui.checkbox(&mut repo.enabled, "");
-                ui.label(&repo.name);
-                ui.label(&repo.url);
+                ui.spinner();
+                ui.label(t!("{}: running...", operation.description()));
             });
         }
 
+        let mut toggled = None;
+        let mut selected = app.settings_modal.selected_repo;
+
+        // Repository table
+        ui.add_enabled_ui(!busy, |ui| egui::Grid::new("repo_table")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label(t!("Priority"));
+            ui.label(t!("Enable"));
+            ui.label(t!("Name"));
+            ui.label(t!("URL"));
+            ui.end_row();
+
+            // Liste pisi işlemi başarılı olunca değişir
+            for (index, repo) in app.settings.repositories.iter().enumerate() {
+                ui.label((index + 1).to_string());
+                let mut enabled = repo.enabled;
+                if ui.checkbox(&mut enabled, "").changed() {
+                    toggled = Some(if enabled {
+                        RepoOperation::Enable(repo.name.clone())
+                    } else {
+                        RepoOperation::Disable(repo.name.clone())
+                    });
+                }
+                if ui.selectable_label(selected == Some(index), &repo.name).clicked() {
+                    selected = Some(index);
+                }
+                ui.label(&repo.url);
+                ui.end_row();
+            }
+        }));
+
+        app.settings_modal.selected_repo = selected.filter(|&i| i < app.settings.repositories.len());
+
+        if let Some(operation) = toggled {
+            app.start_repo_operation(operation);
+        }
+
         ui.separator();
 
         // Repository management buttons
-        ui.horizontal(|ui| {
-            if ui.button("Add Repo").clicked() {
-                app.settings.repositories.push(Repository {
-                    enabled: true,
-                    name: "New Repo".to_string(),
-                                               url: "https://example.com/pisi-index.xml.xz".to_string(),
-                });
+        let has_selection = app.settings_modal.selected_repo.is_some();
+        ui.add_enabled_ui(!busy, |ui| ui.horizontal(|ui| {
+            if ui.button(t!("Add Repo")).clicked() {
+                app.add_repo_dialog = Some(AddRepoDialog::default());
             }
 
-            if ui.button("Remove Repo").clicked() {
-                if !app.settings.repositories.is_empty() {
-                    app.settings.repositories.pop();
-                }
+            if ui.add_enabled(has_selection, egui::Button::new(t!("Remove Repo"))).clicked() {
+                Self::remove_selected_repo(app);
             }
 
-            if ui.button("Move Up").clicked() {
-                // Move repo up implementation
+            if ui.add_enabled(has_selection, egui::Button::new(t!("Move Up"))).clicked() {
+                Self::move_selected_repo(app, true);
             }
 
-            if ui.button("Move Down").clicked() {
-                // Move repo down implementation
+            if ui.add_enabled(has_selection, egui::Button::new(t!("Move Down"))).clicked() {
+                Self::move_selected_repo(app, false);
             }
-        });
+
+            if ui.button(t!("🔄 Refresh")).clicked() {
+                app.sync_repositories();
+            }
+
+            let refreshing = app.index_refresh.is_some();
+            if ui.add_enabled(!refreshing, egui::Button::new(t!("⬇ Refresh Indexes"))).clicked() {
+                app.start_index_refresh();
+            }
+            if refreshing {
+                ui.spinner();
+            }
+        }));
+
+        // Hata ve uyarılar
+        if !app.settings_modal.repo_messages.is_empty() {
+            ui.separator();
+            for message in &app.settings_modal.repo_messages {
+                ui.label(format!("⚠ {}", message));
+            }
+        }
+        false
+    }
+
+    fn remove_selected_repo(app: &mut PackageManagerApp) {
+        let Some(index) = app.settings_modal.selected_repo else {
+            return;
+        };
+        let name = app.settings.repositories[index].name.clone();
+        app.start_repo_operation(RepoOperation::Remove(name));
+    }
+
+    /// Seçili depoyu bir sıra yukarı/aşağı taşı; pisi'deki öncelik arka planda güncellenir
+    fn move_selected_repo(app: &mut PackageManagerApp, up: bool) {
+        let Some(index) = app.settings_modal.selected_repo else {
+            return;
+        };
+        let target = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1).filter(|&i| i < app.settings.repositories.len())
+        };
+        let Some(target) = target else {
+            return;
+        };
+
+        let repo = app.settings.repositories[index].clone();
+        app.start_repo_operation(RepoOperation::Move { repo, from: index, to: target });
     }
 
-    fn render_proxy_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) {
-        ui.heading("Proxy Settings");
+    fn render_proxy_settings(ui: &mut egui::Ui, app: &mut PackageManagerApp) -> bool {
+        let mut changed = false;
+        ui.heading(t!("Proxy Settings"));
 
-        ui.checkbox(&mut app.settings.proxy.use_proxy, "Use proxy");
+        changed |= ui.checkbox(&mut app.settings.proxy.use_proxy, t!("Use proxy")).changed();
 
         if app.settings.proxy.use_proxy {
-            ui.label("HTTP Proxy:");
-            ui.text_edit_singleline(&mut app.settings.proxy.http_proxy);
+            ui.label(t!("HTTP Proxy:"));
+            changed |= ui.text_edit_singleline(&mut app.settings.proxy.http_proxy).changed();
 
-            ui.label("HTTPS Proxy:");
-            ui.text_edit_singleline(&mut app.settings.proxy.https_proxy);
+            ui.label(t!("HTTPS Proxy:"));
+            changed |= ui.text_edit_singleline(&mut app.settings.proxy.https_proxy).changed();
 
-            ui.label("FTP Proxy:");
-            ui.text_edit_singleline(&mut app.settings.proxy.ftp_proxy);
+            ui.label(t!("FTP Proxy:"));
+            changed |= ui.text_edit_singleline(&mut app.settings.proxy.ftp_proxy).changed();
 
-            ui.label("Domain:");
-            ui.text_edit_singleline(&mut app.settings.proxy.domain);
+            ui.label(t!("Domain:"));
+            changed |= ui.text_edit_singleline(&mut app.settings.proxy.domain).changed();
 
             ui.separator();
 
-            ui.heading("Identity Verification");
-            ui.label("Username:");
-            ui.text_edit_singleline(&mut app.settings.proxy.username);
+            ui.heading(t!("Identity Verification"));
+            ui.label(t!("Username:"));
+            changed |= ui.text_edit_singleline(&mut app.settings.proxy.username).changed();
+
+            ui.label(t!("Password:"));
+            changed |= ui.add(egui::TextEdit::singleline(&mut app.settings.proxy.password).password(true)).changed();
 
-            ui.label("Password:");
-            ui.text_edit_singleline(&mut app.settings.proxy.password);
+            changed |= ui.checkbox(&mut app.settings.proxy.use_http_proxy_for_all, t!("Use HTTP Proxy for All")).changed();
+        }
 
-            ui.checkbox(&mut app.settings.proxy.use_http_proxy_for_all, "Use HTTP Proxy for All");
+        ui.separator();
+        if ui.button(t!("Apply to pisi.conf...")).clicked() {
+            app.preview_pisi_conf();
         }
+        changed
     }
 }
@@ -1,14 +1,16 @@
 use eframe::egui;
-use crate::gui::events::{AppEvent, EventManager};
+use crate::{t, tn};
+use crate::gui::app::PackageManagerApp;
+use crate::gui::events::AppEvent;
 
 #[derive(Default)]
 pub struct Sidebar;
 
 impl Sidebar {
-    pub fn render(&self, ui: &mut egui::Ui, event_manager: &mut EventManager) {
+    pub fn render(&self, ui: &mut egui::Ui, app: &PackageManagerApp, events: &mut Vec<AppEvent>) {
         ui.vertical(|ui| {
             // Components section
-            ui.heading("Components");
+            ui.heading(t!("Components"));
             
             // Mock components yerine gerçek data kullanacağız
             // Bu kısım app'ten gelecek
@@ -16,29 +18,36 @@ impl Sidebar {
             ui.separator();
             
             // Categories section
-            ui.heading("Categories");
+            ui.heading(t!("Categories"));
             let categories = [
-                ("Installed", 3127),
-                ("Updates", 15),
-                ("Explorer", 0),
+                ("Installed", app.catalog.installed().len()),
+                ("Updates", app.pending_updates().len()),
+                ("Explorer", app.catalog.len()),
             ];
             
             for (name, count) in categories {
-                let response = ui.selectable_label(false, format!("{} ({})", name, count));
+                let selected = app.selected_category == name && app.search_results.is_none();
+                let response = ui.selectable_label(selected, format!("{} ({})", t!(name), count));
                 
                 if response.clicked() {
-                    event_manager.push(AppEvent::CategorySelected(name.to_string()));
+                    events.push(AppEvent::CategorySelected(name.to_string()));
                 }
             }
             
             ui.separator();
             
+            // Kayıtlı aramalar
+            ui.heading(t!("Smart Collections"));
+            self.render_collections(ui, app, events);
+            
+            ui.separator();
+            
             // Repo messages
-            ui.heading("Repo/Messages");
+            ui.heading(t!("Repo/Messages"));
             let messages = [
-                "Repository synchronized",
-                "6366 packages available", 
-                "197 components loaded",
+                t!("Repository synchronized"),
+                tn!("{n} package available", "{n} packages available", app.catalog.len()),
+                tn!("{n} component loaded", "{n} components loaded", app.catalog.components().len()),
             ];
             
             for message in messages {
@@ -46,4 +55,45 @@ impl Sidebar {
             }
         });
     }
+
+    fn render_collections(&self, ui: &mut egui::Ui, app: &PackageManagerApp, events: &mut Vec<AppEvent>) {
+        let collections = &app.settings.smart_collections;
+        if collections.is_empty() {
+            ui.label(t!("Save a search from the filter bar to pin it here"));
+        }
+        
+        for (index, collection) in collections.iter().enumerate() {
+            let count = app.collection_counts.get(index).copied().unwrap_or(0);
+            let text = format!("⭐ {} ({})", collection.name, count);
+            let mut hover = if collection.query.trim().is_empty() {
+                t!("Search: all packages")
+            } else {
+                t!("Search: {}", collection.query)
+            };
+            hover.push('\n');
+            hover.push_str(&t!("Active filters: {}", collection.filter.chips().len()));
+            
+            let response = ui
+                .selectable_label(app.is_collection_active(collection), text)
+                .on_hover_text(hover);
+            if response.clicked() {
+                events.push(AppEvent::ApplyCollection(index));
+            }
+            response.context_menu(|ui| {
+                if ui.button(t!("🗑️ Delete")).clicked() {
+                    events.push(AppEvent::DeleteCollection(index));
+                    ui.close_menu();
+                }
+            });
+        }
+        
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!collections.is_empty(), egui::Button::new(t!("Export"))).clicked() {
+                events.push(AppEvent::ExportCollections);
+            }
+            if ui.button(t!("Import")).clicked() {
+                events.push(AppEvent::ImportCollections);
+            }
+        });
+    }
 }
@@ -1,10 +1,46 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use crate::backend::catalog::PackageId;
+use crate::backend::filter::PackageFilter;
+use crate::backend::package_manager::PackageOperation;
+use crate::backend::repository::RepoOperation;
+
 #[derive(Debug, Clone)]
 pub enum AppEvent {
     CategorySelected(String),
     ComponentSelected(String),
     ShowSettings,
+    SelectPackage(PackageId),
+    TogglePackageHold(String),
+    OpenLocalPackage(PathBuf),
+    RunOperation(PackageOperation),
+    FilterChanged(PackageFilter),
+    /// `settings.smart_collections` içindeki sıra
+    ApplyCollection(usize),
+    SaveCollection,
+    DeleteCollection(usize),
+    ExportCollections,
+    ImportCollections,
+    /// Arka plan işlerinin sonuçları; hatalar mesaj olarak taşınır
+    PackageStatesLoaded {
+        installed: Result<Vec<String>, String>,
+        versions: Result<HashMap<String, String>, String>,
+    },
+    UpdatesChecked(Result<Vec<String>, String>),
+    OperationFinished {
+        operation: PackageOperation,
+        result: Result<(), String>,
+    },
+    RepoOperationFinished {
+        operation: RepoOperation,
+        result: Result<(), String>,
+    },
+    /// Sınırı aşan önbellek dosyaları silinmeye başlandı
+    CacheTrimStarted { files: usize, size: u64 },
+    CacheTrimFinished(Result<u64, String>),
 }
 
+#[derive(Default)]
 pub struct EventManager {
     events: Vec<AppEvent>,
 }
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Masaüstünün dosya seçicisini aç (önce zenity, sonra kdialog)
+pub fn pick_pisi_file() -> Option<PathBuf> {
+    run_dialog([
+        ("zenity", &["--file-selection", "--title=Open Pisi Package", "--file-filter=*.pisi"]),
+        ("kdialog", &["--getopenfilename", ".", "*.pisi"]),
+    ])
+}
+
+/// İçe aktarılacak .json dosyasını seç
+pub fn pick_json_file() -> Option<PathBuf> {
+    run_dialog([
+        ("zenity", &["--file-selection", "--title=Import", "--file-filter=*.json"]),
+        ("kdialog", &["--getopenfilename", ".", "*.json"]),
+    ])
+}
+
+/// Dışa aktarılacak .json dosyasının yerini sor
+pub fn save_json_file(default_name: &str) -> Option<PathBuf> {
+    let filename = format!("--filename={}", default_name);
+    run_dialog([
+        ("zenity", &["--file-selection", "--save", "--confirm-overwrite", "--title=Export", &filename]),
+        ("kdialog", &["--getsavefilename", default_name, "*.json"]),
+    ])
+}
+
+fn run_dialog<const N: usize>(dialogs: [(&str, &[&str]); N]) -> Option<PathBuf> {
+    for (program, args) in dialogs {
+        match Command::new(program).args(args).output() {
+            Ok(output) => {
+                // İptal edildiğinde boş çıktı ve sıfır olmayan çıkış kodu gelir
+                let selected = String::from_utf8_lossy(&output.stdout).trim().to_string();
+                if output.status.success() && !selected.is_empty() {
+                    return Some(PathBuf::from(selected));
+                }
+                return None;
+            }
+            Err(_) => continue,
+        }
+    }
+
+    println!("No file dialog available (install zenity or kdialog)");
+    None
+}
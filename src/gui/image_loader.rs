@@ -1,6 +1,7 @@
 use eframe::egui;
 use std::collections::HashMap;
 
+#[derive(Default)]
 pub struct ImageLoader {
     textures: HashMap<String, egui::TextureHandle>,
     loaded_textures: HashMap<String, bool>,
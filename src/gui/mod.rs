@@ -1,7 +1,7 @@
 pub mod app;
 pub mod components;
-pub mod themes;
 pub mod events;
 pub mod image_loader;
+pub mod file_dialog;
 
 pub use app::PackageManagerApp;
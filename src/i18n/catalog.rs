@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+/// gettext .po dosyasından okunan çeviriler
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    messages: HashMap<String, Vec<String>>,
+    plural_rule: PluralRule,
+}
+
+/// Plural-Forms başlığındaki kural; yalnızca yaygın biçimler desteklenir
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum PluralRule {
+    /// nplurals=1; plural=0;
+    Single,
+    /// plural=(n != 1);
+    #[default]
+    NotOne,
+    /// plural=(n > 1);
+    GreaterThanOne,
+}
+
+impl PluralRule {
+    fn parse(header: &str) -> Self {
+        let Some(forms) = header
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Plural-Forms:"))
+        else {
+            return Self::default();
+        };
+
+        let compact: String = forms.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.contains("nplurals=1") {
+            Self::Single
+        } else if compact.contains("n>1") {
+            Self::GreaterThanOne
+        } else {
+            Self::NotOne
+        }
+    }
+
+    fn index(&self, n: u64) -> usize {
+        match self {
+            Self::Single => 0,
+            Self::NotOne => usize::from(n != 1),
+            Self::GreaterThanOne => usize::from(n > 1),
+        }
+    }
+}
+
+/// Ayrıştırma sırasında okunan tek kayıt
+#[derive(Default)]
+struct Entry {
+    msgid: String,
+    msgid_plural: Option<String>,
+    msgstr: Vec<String>,
+}
+
+enum Field {
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+impl Catalog {
+    pub fn parse(content: &str) -> Self {
+        let mut catalog = Self::default();
+        let mut entry = Entry::default();
+        let mut field = None;
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                catalog.finish(std::mem::take(&mut entry));
+                field = None;
+                continue;
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("msgid_plural ") {
+                entry.msgid_plural = Some(unquote(rest));
+                field = Some(Field::IdPlural);
+            } else if let Some(rest) = line.strip_prefix("msgid ") {
+                // Boş satır olmadan başlayan yeni kayıt
+                if field.is_some() && !entry.msgstr.is_empty() {
+                    catalog.finish(std::mem::take(&mut entry));
+                }
+                entry.msgid = unquote(rest);
+                field = Some(Field::Id);
+            } else if let Some(rest) = line.strip_prefix("msgstr[") {
+                let Some((index, value)) = rest.split_once(']') else {
+                    continue;
+                };
+                let index: usize = index.parse().unwrap_or(0);
+                if entry.msgstr.len() <= index {
+                    entry.msgstr.resize(index + 1, String::new());
+                }
+                entry.msgstr[index] = unquote(value.trim());
+                field = Some(Field::Str(index));
+            } else if let Some(rest) = line.strip_prefix("msgstr ") {
+                entry.msgstr = vec![unquote(rest)];
+                field = Some(Field::Str(0));
+            } else if line.starts_with('"') {
+                // Önceki alanın devam satırı
+                let value = unquote(line);
+                match field {
+                    Some(Field::Id) => entry.msgid.push_str(&value),
+                    Some(Field::IdPlural) => {
+                        if let Some(plural) = &mut entry.msgid_plural {
+                            plural.push_str(&value);
+                        }
+                    }
+                    Some(Field::Str(index)) => {
+                        if let Some(text) = entry.msgstr.get_mut(index) {
+                            text.push_str(&value);
+                        }
+                    }
+                    None => {}
+                }
+            }
+        }
+        catalog.finish(entry);
+        catalog
+    }
+
+    fn finish(&mut self, entry: Entry) {
+        if entry.msgid.is_empty() {
+            // Başlık kaydı
+            if let Some(header) = entry.msgstr.first() {
+                self.plural_rule = PluralRule::parse(header);
+            }
+            return;
+        }
+        // Çevrilmemiş kayıtlar msgid'ye düşsün
+        if entry.msgstr.iter().all(String::is_empty) {
+            return;
+        }
+        self.messages.insert(entry.msgid, entry.msgstr);
+    }
+
+    pub fn get(&self, msgid: &str) -> Option<&str> {
+        self.messages.get(msgid)?.first().map(String::as_str)
+    }
+
+    /// Sayıya uygun çoğul biçim; çeviri yoksa İngilizce kural kullanılır
+    pub fn get_plural<'a>(&'a self, singular: &'a str, plural: &'a str, n: u64) -> &'a str {
+        let english = if n == 1 { singular } else { plural };
+        let Some(forms) = self.messages.get(singular) else {
+            return english;
+        };
+        forms
+            .get(self.plural_rule.index(n))
+            .filter(|form| !form.is_empty())
+            .map(String::as_str)
+            .unwrap_or(english)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// "\"Merhaba\\n\"" -> "Merhaba\n"
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+
+    let mut result = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result
+}
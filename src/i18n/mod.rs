@@ -0,0 +1,150 @@
+//! Arayüz çevirileri
+//!
+//! Kaynak dil İngilizcedir; msgid'ler İngilizce metnin kendisidir ve çevirisi
+//! olmayan metinler olduğu gibi gösterilir. Kataloglar `locales/*.po`
+//! dosyalarından derleme sırasında gömülür.
+
+mod catalog;
+
+pub use catalog::Catalog;
+
+use std::fmt::Display;
+use std::sync::{LazyLock, RwLock};
+
+/// Desteklenen diller: (kod, kendi dilindeki adı, katalog)
+pub const LANGUAGES: [(&str, &str, &str); 2] = [
+    ("en", "English", include_str!("../../locales/en.po")),
+    ("tr", "Türkçe", include_str!("../../locales/tr.po")),
+];
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+struct Active {
+    language: &'static str,
+    catalog: Catalog,
+}
+
+static ACTIVE: LazyLock<RwLock<Active>> = LazyLock::new(|| {
+    RwLock::new(Active {
+        language: DEFAULT_LANGUAGE,
+        catalog: Catalog::default(),
+    })
+});
+
+/// Dili değiştir; boş ya da desteklenmeyen kod ortamdaki dile düşer
+pub fn set_language(code: &str) {
+    let code = if code.is_empty() { detect_language() } else { code };
+    let Some((code, _, content)) = LANGUAGES.iter().find(|(c, _, _)| *c == code) else {
+        println!("Unsupported language {}, using {}", code, DEFAULT_LANGUAGE);
+        return set_language(DEFAULT_LANGUAGE);
+    };
+
+    let catalog = Catalog::parse(content);
+    println!("Language: {} ({} translated messages)", code, catalog.len());
+    if let Ok(mut active) = ACTIVE.write() {
+        active.language = code;
+        active.catalog = catalog;
+    }
+}
+
+pub fn current_language() -> &'static str {
+    ACTIVE.read().map(|active| active.language).unwrap_or(DEFAULT_LANGUAGE)
+}
+
+/// LANGUAGE, LC_ALL, LC_MESSAGES ve LANG sırasıyla; "tr_TR.UTF-8" -> "tr"
+pub fn detect_language() -> &'static str {
+    for variable in ["LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"] {
+        let Ok(value) = std::env::var(variable) else {
+            continue;
+        };
+        // LANGUAGE birden çok dil içerebilir: "tr:en"
+        for locale in value.split(':') {
+            let code = locale
+                .split(['_', '.', '@'])
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+            if let Some((code, _, _)) = LANGUAGES.iter().find(|(c, _, _)| *c == code) {
+                return code;
+            }
+        }
+        // "C" ya da "POSIX" gibi değerler bir sonraki değişkene bakmaz
+        if !value.is_empty() {
+            break;
+        }
+    }
+    DEFAULT_LANGUAGE
+}
+
+/// Metnin etkin dildeki karşılığı
+pub fn tr(msgid: &str) -> String {
+    match ACTIVE.read() {
+        Ok(active) => active.catalog.get(msgid).unwrap_or(msgid).to_string(),
+        Err(_) => msgid.to_string(),
+    }
+}
+
+/// Sayıya göre tekil/çoğul biçim; `{n}` sayıyla değiştirilir
+pub fn trn(singular: &str, plural: &str, n: u64) -> String {
+    let text = match ACTIVE.read() {
+        Ok(active) => active.catalog.get_plural(singular, plural, n).to_string(),
+        Err(_) => (if n == 1 { singular } else { plural }).to_string(),
+    };
+    text.replace("{n}", &n.to_string())
+}
+
+/// `{}` sıradaki, `{0}`, `{1}` belirtilen argümanla değiştirilir
+///
+/// Çevirmen kelime sırasını değiştirebilsin diye numaralı biçim de desteklenir.
+pub fn format(template: &str, args: &[&dyn Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut next = 0;
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+
+        let key = &after[..end];
+        let index = if key.is_empty() {
+            next += 1;
+            Some(next - 1)
+        } else {
+            key.parse::<usize>().ok()
+        };
+        match index.and_then(|i| args.get(i)) {
+            Some(arg) => result.push_str(&arg.to_string()),
+            // {n} gibi bilinmeyen yer tutucular korunur
+            None => result.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Çevir ve argümanları yerleştir: `t!("Version: {}", version)`
+#[macro_export]
+macro_rules! t {
+    ($msgid:expr) => {
+        $crate::i18n::tr($msgid)
+    };
+    ($msgid:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format(&$crate::i18n::tr($msgid), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+
+/// Çoğul biçimli çeviri: `tn!("{n} package", "{n} packages", count)`
+#[macro_export]
+macro_rules! tn {
+    ($singular:expr, $plural:expr, $n:expr) => {
+        $crate::i18n::trn($singular, $plural, $n as u64)
+    };
+    ($singular:expr, $plural:expr, $n:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format(&$crate::i18n::trn($singular, $plural, $n as u64), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
@@ -1,3 +1,4 @@
 pub mod config;
 pub mod backend;
 pub mod gui;
+pub mod i18n;
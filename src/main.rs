@@ -1,9 +1,5 @@
-mod config;
-mod backend;
-mod gui;
-
 use eframe::egui;
-use gui::PackageManagerApp;
+use pisi_package_manager::gui::PackageManagerApp;
 
 fn main() -> eframe::Result<()> {
     // Asset dosyalarının varlığını kontrol et
@@ -14,8 +10,8 @@ fn main() -> eframe::Result<()> {
             .with_inner_size([1200.0, 800.0])
             .with_title("Pisi GNU/Linux Software Center"),
         
+        // Tema uygulama tarafından (ayarlar ve masaüstü tercihi) belirlenir
         follow_system_theme: false,
-        default_theme: eframe::Theme::Dark,
         
         ..Default::default()
     };
@@ -24,8 +20,14 @@ fn main() -> eframe::Result<()> {
         "Pisi Package Manager",
         options,
         Box::new(|cc| {
-            cc.egui_ctx.set_visuals(egui::Visuals::dark());
-            Box::new(PackageManagerApp::new(cc))
+            let mut app = PackageManagerApp::new(cc);
+            
+            // Komut satırından verilen .pisi dosyası
+            if let Some(path) = std::env::args().nth(1) {
+                app.open_local_package(std::path::Path::new(&path));
+            }
+            
+            Box::new(app)
         }),
     )
 }
@@ -0,0 +1,250 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
+use pisi_package_manager::backend::downloader::{DownloadOptions, DownloadOutcome, Downloader};
+use pisi_package_manager::config::{AppSettings, ProxySettings};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+const BODY: &[u8] = b"<PISI><Package><Name>hello</Name></Package></PISI>\n";
+
+/// Her bağlantıya `handler`'ın yanıtını yazan yerel HTTP sunucusu; gelen
+/// istek başlıkları sırayla kaydedilir
+struct TestServer {
+    address: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestServer {
+    fn start(handler: impl Fn(&str) -> Vec<u8> + Send + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else { continue };
+                let mut head = Vec::new();
+                let mut byte = [0u8; 1];
+                while !head.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap_or(0) == 1 {
+                    head.push(byte[0]);
+                }
+                let head = String::from_utf8_lossy(&head).to_string();
+                let response = handler(&head);
+                log.lock().unwrap().push(head);
+                let _ = stream.write_all(&response);
+            }
+        });
+
+        Self { address, requests }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.address, path)
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn response(status: &str, headers: &[&str], body: &[u8]) -> Vec<u8> {
+    let mut out = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n", status, body.len());
+    for header in headers {
+        out.push_str(header);
+        out.push_str("\r\n");
+    }
+    out.push_str("\r\n");
+    let mut out = out.into_bytes();
+    out.extend_from_slice(body);
+    out
+}
+
+fn has_header(head: &str, header: &str) -> bool {
+    head.lines().any(|line| line.eq_ignore_ascii_case(header))
+}
+
+fn temp_dest(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pisi-downloader-test-{}-{}", test, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("pisi-index.xml")
+}
+
+fn proxy_settings(address: &str) -> ProxySettings {
+    ProxySettings {
+        use_proxy: true,
+        http_proxy: address.to_string(),
+        https_proxy: String::new(),
+        ftp_proxy: String::new(),
+        domain: String::new(),
+        username: String::new(),
+        password: String::new(),
+        use_http_proxy_for_all: false,
+    }
+}
+
+#[test]
+fn revalidates_with_etag_and_gets_not_modified() {
+    let server = TestServer::start(|head| {
+        if has_header(head, "If-None-Match: \"v1\"") {
+            response("304 Not Modified", &["ETag: \"v1\""], b"")
+        } else {
+            response("200 OK", &["ETag: \"v1\""], BODY)
+        }
+    });
+    let dest = temp_dest("etag");
+    let downloader = Downloader::default();
+
+    let first = downloader.download_to(&server.url("/pisi-index.xml"), &dest).unwrap();
+    assert_eq!(first, DownloadOutcome::Downloaded(BODY.len() as u64));
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+
+    let second = downloader.download_to(&server.url("/pisi-index.xml"), &dest).unwrap();
+    assert_eq!(second, DownloadOutcome::NotModified);
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    assert!(has_header(&server.requests()[1], "If-None-Match: \"v1\""));
+}
+
+#[test]
+fn resumes_partial_download_with_range() {
+    let split = 10;
+    let server = TestServer::start(move |head| {
+        if has_header(head, &format!("Range: bytes={}-", split)) {
+            let range = format!("Content-Range: bytes {}-{}/{}", split, BODY.len() - 1, BODY.len());
+            response("206 Partial Content", &["ETag: \"v1\"", &range], &BODY[split..])
+        } else {
+            response("200 OK", &["ETag: \"v1\""], BODY)
+        }
+    });
+    let dest = temp_dest("resume");
+    std::fs::write(dest.with_file_name("pisi-index.xml.part"), &BODY[..split]).unwrap();
+    std::fs::write(dest.with_file_name("pisi-index.xml.part.meta"), r#"{"etag":"\"v1\"","last_modified":null}"#).unwrap();
+
+    let outcome = Downloader::default().download_to(&server.url("/pisi-index.xml"), &dest).unwrap();
+
+    assert_eq!(outcome, DownloadOutcome::Downloaded(BODY.len() as u64));
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    assert!(!dest.with_file_name("pisi-index.xml.part").exists());
+    assert!(has_header(&server.requests()[0], "If-Range: \"v1\""));
+}
+
+#[test]
+fn restarts_when_server_ignores_range() {
+    let server = TestServer::start(|_| response("200 OK", &["ETag: \"v2\""], BODY));
+    let dest = temp_dest("restart");
+    std::fs::write(dest.with_file_name("pisi-index.xml.part"), b"stale bytes").unwrap();
+    std::fs::write(dest.with_file_name("pisi-index.xml.part.meta"), r#"{"etag":"\"v1\"","last_modified":null}"#).unwrap();
+
+    let outcome = Downloader::default().download_to(&server.url("/pisi-index.xml"), &dest).unwrap();
+
+    assert_eq!(outcome, DownloadOutcome::Downloaded(BODY.len() as u64));
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    assert!(has_header(&server.requests()[0], "If-Range: \"v1\""));
+}
+
+#[test]
+fn restarts_partial_download_without_validator() {
+    let server = TestServer::start(|_| response("200 OK", &[], BODY));
+    let dest = temp_dest("no-validator");
+    std::fs::write(dest.with_file_name("pisi-index.xml.part"), b"stale bytes").unwrap();
+
+    let outcome = Downloader::default().download_to(&server.url("/pisi-index.xml"), &dest).unwrap();
+
+    assert_eq!(outcome, DownloadOutcome::Downloaded(BODY.len() as u64));
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    assert!(!server.requests()[0].contains("Range:"));
+}
+
+#[test]
+fn resumes_with_last_modified_when_there_is_no_etag() {
+    let split = 10;
+    let modified = "Last-Modified: Tue, 13 Oct 2026 08:00:00 GMT";
+    let server = TestServer::start(move |head| {
+        if has_header(head, &format!("Range: bytes={}-", split)) {
+            let range = format!("Content-Range: bytes {}-{}/{}", split, BODY.len() - 1, BODY.len());
+            response("206 Partial Content", &[&range], &BODY[split..])
+        } else {
+            // Bağlantı gövde bitmeden kapanır
+            let mut partial = response("200 OK", &[modified], BODY);
+            partial.truncate(partial.len() - (BODY.len() - split));
+            partial
+        }
+    });
+    let dest = temp_dest("last-modified");
+    let downloader = Downloader::default();
+
+    assert!(downloader.download_to(&server.url("/pisi-index.xml"), &dest).is_err());
+    assert_eq!(std::fs::read(dest.with_file_name("pisi-index.xml.part")).unwrap(), &BODY[..split]);
+
+    let outcome = downloader.download_to(&server.url("/pisi-index.xml"), &dest).unwrap();
+
+    assert_eq!(outcome, DownloadOutcome::Downloaded(BODY.len() as u64));
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    assert!(has_header(&server.requests()[1], "If-Range: Tue, 13 Oct 2026 08:00:00 GMT"));
+    assert!(!dest.with_file_name("pisi-index.xml.part.meta").exists());
+    // Tamamlanan dosya sonraki indirmede Last-Modified ile doğrulanır
+    let meta = std::fs::read_to_string(dest.with_file_name("pisi-index.xml.meta")).unwrap();
+    assert!(meta.contains("Tue, 13 Oct 2026 08:00:00 GMT"));
+}
+
+#[test]
+fn does_not_resume_over_a_weak_etag_alone() {
+    let server = TestServer::start(|_| response("200 OK", &["ETag: W/\"v3\""], BODY));
+    let dest = temp_dest("weak-etag");
+    std::fs::write(dest.with_file_name("pisi-index.xml.part"), b"stale bytes").unwrap();
+    std::fs::write(dest.with_file_name("pisi-index.xml.part.meta"), r#"{"etag":"W/\"v3\"","last_modified":null}"#).unwrap();
+
+    let outcome = Downloader::default().download_to(&server.url("/pisi-index.xml"), &dest).unwrap();
+
+    assert_eq!(outcome, DownloadOutcome::Downloaded(BODY.len() as u64));
+    assert_eq!(std::fs::read(&dest).unwrap(), BODY);
+    assert!(!server.requests()[0].contains("Range:"));
+}
+
+#[test]
+fn sends_requests_through_proxy_with_raw_credentials() {
+    let proxy = TestServer::start(|_| response("200 OK", &[], BODY));
+    let mut settings = proxy_settings(&proxy.address);
+    settings.domain = "CORP".to_string();
+    settings.username = "ali".to_string();
+    settings.password = "p@ss:w/rd%é".to_string();
+    let options = DownloadOptions::from_settings(&settings, &AppSettings::default().cache);
+
+    let bytes = Downloader::new(options).fetch("http://repo.example.org/pisi-index.xml").unwrap();
+
+    assert_eq!(bytes, BODY);
+    let head = &proxy.requests()[0];
+    assert!(head.starts_with("GET http://repo.example.org/pisi-index.xml HTTP/1.1\r\n"), "{}", head);
+    let expected = BASE64_STANDARD.encode("CORP\\ali:p@ss:w/rd%é");
+    assert!(has_header(head, &format!("Proxy-Authorization: Basic {}", expected)), "{}", head);
+}
+
+#[test]
+fn proxy_without_credentials_sends_no_authorization() {
+    let proxy = TestServer::start(|_| response("200 OK", &[], BODY));
+    let options = DownloadOptions::from_settings(&proxy_settings(&proxy.address), &AppSettings::default().cache);
+
+    Downloader::new(options).fetch("http://repo.example.org/pisi-index.xml").unwrap();
+
+    assert!(!proxy.requests()[0].to_ascii_lowercase().contains("proxy-authorization"));
+}
+
+#[test]
+fn normalizes_proxy_addresses() {
+    let mut settings = proxy_settings("https://proxy.local:3128/");
+    settings.https_proxy = "proxy.local:3129".to_string();
+    settings.ftp_proxy = "ftp-proxy.local:2121".to_string();
+    let options = DownloadOptions::from_settings(&settings, &AppSettings::default().cache);
+
+    assert_eq!(options.http_proxy.as_deref(), Some("http://proxy.local:3128"));
+    assert_eq!(options.https_proxy.as_deref(), Some("http://proxy.local:3129"));
+    assert_eq!(options.ftp_proxy.as_deref(), Some("http://ftp-proxy.local:2121"));
+    assert_eq!(options.proxy_credentials, None);
+
+    settings.use_http_proxy_for_all = true;
+    let options = DownloadOptions::from_settings(&settings, &AppSettings::default().cache);
+    assert_eq!(options.ftp_proxy, options.http_proxy);
+    assert_eq!(options.https_proxy, options.http_proxy);
+}
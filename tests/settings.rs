@@ -0,0 +1,89 @@
+use pisi_package_manager::config::{AppSettings, Theme, SETTINGS_SCHEMA_VERSION};
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+fn temp_settings(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("pisi-settings-test-{}-{}", test, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join("settings.json")
+}
+
+#[test]
+fn saves_settings_readable_only_by_user() {
+    let path = temp_settings("mode");
+    AppSettings::default().save_to(&path).unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert!(AppSettings::load_from(&path).0.is_some());
+}
+
+#[test]
+fn corrupt_file_is_moved_aside_and_treated_as_first_run() {
+    let path = temp_settings("corrupt");
+    std::fs::write(&path, "{ not json").unwrap();
+
+    let (settings, warning) = AppSettings::load_from(&path);
+
+    assert!(settings.is_none());
+    assert!(warning.is_some());
+    assert!(!path.exists());
+    assert!(path.with_extension("json.corrupt").exists());
+}
+
+#[test]
+fn does_not_overwrite_file_from_newer_schema() {
+    let path = temp_settings("newer");
+    let mut value = serde_json::to_value(AppSettings::default()).unwrap();
+    value["schema_version"] = 999.into();
+    value["future_field"] = "keep me".into();
+    let content = serde_json::to_string(&value).unwrap();
+    std::fs::write(&path, &content).unwrap();
+
+    let (settings, warning) = AppSettings::load_from(&path);
+    assert!(warning.is_some());
+    assert!(settings.unwrap().save_to(&path).is_err());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), content);
+}
+
+#[test]
+fn migrates_versionless_file_and_keeps_user_values() {
+    let path = temp_settings("versionless");
+    // İlk sürümün yazdığı dosya: schema_version ve sonradan eklenen alanlar yok
+    std::fs::write(&path, r#"{
+        "package_list": { "show_only_desktop_apps": true, "show_components_info": true, "show_package_type_labels": false },
+        "update": { "check_updates": false, "update_check_interval": 120, "install_updates_automatically": false },
+        "cache": { "use_disk_cache": true, "cache_size_mb": 2048, "cache_directory": "/var/cache/pisi/packages" },
+        "proxy": {
+            "use_proxy": true, "http_proxy": "http://proxy:3128", "https_proxy": "", "ftp_proxy": "",
+            "domain": "", "username": "user", "password": "secret", "use_http_proxy_for_all": false
+        },
+        "repositories": [ { "enabled": true, "name": "pisi-2.0", "url": "https://example.org/pisi-index.xml.xz" } ],
+        "system_tray_enabled": false
+    }"#).unwrap();
+
+    let (settings, warning) = AppSettings::load_from(&path);
+    let settings = settings.unwrap();
+    assert!(warning.is_none());
+    assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+    assert!(settings.package_list.show_only_desktop_apps);
+    assert_eq!(settings.update.update_check_interval, 120);
+    assert_eq!(settings.cache.cache_size_mb, 2048);
+    assert_eq!(settings.proxy.password, "secret");
+    assert_eq!(settings.repositories.len(), 1);
+    // Eksik alanlar varsayılanlarla dolar
+    assert!(!settings.cache.use_bandwidth_limit);
+    assert!(!settings.cache.trim_after_operations);
+    assert!(settings.held_packages.is_empty());
+    assert_eq!(settings.theme, Theme::default());
+    assert!(settings.smart_collections.is_empty());
+
+    settings.save_to(&path).unwrap();
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(saved["schema_version"], SETTINGS_SCHEMA_VERSION);
+    assert_eq!(saved["cache"]["cache_size_mb"], 2048);
+    assert_eq!(saved["cache"]["trim_after_operations"], false);
+    assert_eq!(saved["held_packages"], serde_json::json!([]));
+    assert_eq!(saved["proxy"]["username"], "user");
+}